@@ -0,0 +1,1177 @@
+use crate::TreeError;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Tag of the next [ArenaTree] created.
+static NEXT_ARENA: AtomicU64 = AtomicU64::new(0);
+
+/// Identifier of a node stored in an [ArenaTree].
+///
+/// A NodeId is an index in the arena together with a generation. When a node is removed from the
+/// arena (through [ArenaTree::split] or [ArenaTree::into_vec]), the generation of its slot is
+/// incremented, so that an old NodeId is detected as stale instead of silently pointing at
+/// whatever node reused the slot.
+///
+/// Every arena also has its own tag, stored in the NodeIds it creates, so a NodeId is only valid
+/// in the arena it comes from: a NodeId used with another arena (such as the tree returned by
+/// [ArenaTree::split], a clone, or the tree a subtree was joined into) is stale as well.
+///
+/// # Examples
+/// ```
+/// # use libtree::ArenaTree;
+/// let mut tree = ArenaTree::from_element(1);
+/// tree.push(2);
+/// let split_tree = tree.split(0);
+/// assert_eq!(split_tree.get(tree.root_id()), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId {
+    arena: u64,
+    index: usize,
+    generation: u32,
+}
+
+/// Node stored in an arena. Father and childs are plain indices, as they are always valid inside
+/// the arena that stores the node.
+#[derive(Clone)]
+struct ArenaNode<T> {
+    father: Option<usize>,
+    childs: Vec<usize>,
+    elem: T,
+}
+
+#[derive(Clone)]
+struct Slot<T> {
+    generation: u32,
+    node: Option<ArenaNode<T>>,
+}
+
+/// A tree storing its nodes in a contiguous [Vec] instead of individual allocations
+///
+/// ## Data structure
+/// An [ArenaTree] stores every node in a Vec of slots, and nodes refer to each others through
+/// their index in this Vec. Removed nodes leave a free slot that will be reused by the next
+/// pushed node. Compared to [crate::Tree], traversals are not pointer chases anymore, there is
+/// one allocation for the whole tree instead of one per node, and no unsafe code is needed
+/// (except for [ArenaTree::iter_mut]).
+///
+/// The price to pay is that [ArenaTree::split] and [ArenaTree::join] have to move every node of
+/// the moved subtree from one arena to the other, so they are linear in the size of the subtree
+/// (instead of constant for [crate::Tree]).
+///
+/// ArenaTree offers the same navigation API as [crate::Tree] ('current', 'root', cursors,
+/// depth-first iteration), so that code written for one can be switched to the other. Like
+/// [crate::Tree], every method will panic if called on an empty tree, unless written otherwise.
+///
+/// # Examples
+/// ```
+/// # use libtree::ArenaTree;
+/// let mut tree = ArenaTree::from_element(1);
+/// tree.push_iter(vec![2, 3]);
+/// tree.navigate_to(0);
+/// tree.push(4);
+/// tree.go_to_root();
+/// assert_eq!(tree.iter().collect::<Vec<&i32>>(), vec![&1, &2, &4, &3]);
+/// ```
+pub struct ArenaTree<T> {
+    /// Tag of the arena, unique among the arenas of the program.
+    arena: u64,
+    slots: Vec<Slot<T>>,
+    free: Vec<usize>,
+    root: Option<usize>,
+    current: Option<usize>,
+    len: usize,
+}
+
+impl<T> ArenaTree<T> {
+    /// Creates an [ArenaTree] from el. root and current will be pointing to the node holding el.
+    pub fn from_element(el: T) -> Self {
+        let mut tree = ArenaTree::default();
+        let root = tree.alloc(el, None);
+        tree.root = Some(root);
+        tree.current = Some(root);
+        tree
+    }
+
+    /// Store a new node in a free slot (or a new one) and return its index.
+    fn alloc(&mut self, el: T, father: Option<usize>) -> usize {
+        let node = ArenaNode {
+            father,
+            childs: Vec::new(),
+            elem: el,
+        };
+        self.len += 1;
+        match self.free.pop() {
+            Some(index) => {
+                self.slots[index].node = Some(node);
+                index
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    node: Some(node),
+                });
+                self.slots.len() - 1
+            }
+        }
+    }
+
+    /// Remove the node stored at index, invalidating every [NodeId] pointing at it.
+    fn dealloc(&mut self, index: usize) -> ArenaNode<T> {
+        let slot = &mut self.slots[index];
+        let node = slot.node.take().expect("dealloc called on a free slot");
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(index);
+        self.len -= 1;
+        node
+    }
+
+    fn node(&self, index: usize) -> &ArenaNode<T> {
+        self.slots[index].node.as_ref().unwrap()
+    }
+
+    fn node_mut(&mut self, index: usize) -> &mut ArenaNode<T> {
+        self.slots[index].node.as_mut().unwrap()
+    }
+
+    fn current_index(&self) -> Result<usize, TreeError> {
+        self.current.ok_or(TreeError::EmptyTree)
+    }
+
+    fn id_of(&self, index: usize) -> NodeId {
+        NodeId {
+            arena: self.arena,
+            index,
+            generation: self.slots[index].generation,
+        }
+    }
+
+    /// Return the index of the node pointed by id, if id is not stale.
+    fn index_of(&self, id: NodeId) -> Option<usize> {
+        if id.arena != self.arena {
+            return None;
+        }
+        match self.slots.get(id.index) {
+            Some(slot) if slot.generation == id.generation && slot.node.is_some() => Some(id.index),
+            _ => None,
+        }
+    }
+
+    /// Return true if the tree is empty, i.e. if 'root' = None.
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Return the number of nodes stored in the whole tree.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::ArenaTree;
+    /// let mut tree = ArenaTree::from_element(1);
+    /// tree.push_iter(vec![2, 3]);
+    /// assert_eq!(tree.len(), 3);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Push el to 'current'.child as a new node in the tree.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::ArenaTree;
+    /// let mut tree = ArenaTree::from_element(1);
+    /// tree.push(2);
+    /// tree.navigate_to(0);
+    /// assert_eq!(tree.peek(), &2);
+    /// ```
+    pub fn push(&mut self, el: T) {
+        if self.is_empty() {
+            panic!("Tried to push an element to an empty tree")
+        }
+        let current = self.current.unwrap();
+        let child = self.alloc(el, Some(current));
+        self.node_mut(current).childs.push(child);
+    }
+
+    /// Convenient method to push the elements of an iterator into the tree.
+    pub fn push_iter<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for el in iter.into_iter() {
+            self.push(el);
+        }
+    }
+
+    /// Insert el into 'current'.childs at index.
+    ///
+    /// # Panics
+    /// This method will panic if index > tree.childs_len()
+    pub fn insert(&mut self, index: usize, el: T) {
+        if self.is_empty() {
+            panic!("Tried to insert an element to an empty tree");
+        }
+        let current = self.current.unwrap();
+        let len = self.childs_len();
+        if index > len {
+            panic!(
+                "Tried to insert at index {} but current has only {} childs",
+                index, len
+            );
+        }
+        let child = self.alloc(el, Some(current));
+        self.node_mut(current).childs.insert(index, child);
+    }
+
+    /// Set current to 'current'.childs\[index\], therefore navigating current to it's idx childs.
+    ///
+    /// # Panics
+    /// This method will panic if index >= tree.childs_len()
+    pub fn navigate_to(&mut self, index: usize) {
+        if self.is_empty() {
+            panic!("Tried to move to with an empty tree");
+        }
+        if let Err(TreeError::ChildOutOfRange { index, len }) = self.try_navigate_to(index) {
+            panic!(
+                "Tried to move to children {} of current node, but current node has only {} childs",
+                index, len
+            );
+        }
+    }
+
+    /// Same as [ArenaTree::navigate_to], but returns an error instead of panicking.
+    pub fn try_navigate_to(&mut self, index: usize) -> Result<(), TreeError> {
+        let current_node = self.node(self.current_index()?);
+        match current_node.childs.get(index) {
+            Some(&child) => {
+                self.current = Some(child);
+                Ok(())
+            }
+            None => Err(TreeError::ChildOutOfRange {
+                index,
+                len: current_node.childs.len(),
+            }),
+        }
+    }
+
+    /// Set current to 'current'.father, therefore naviguating current to it's father
+    ///
+    /// # Panics
+    /// This method will panic if 'current' has no father.
+    pub fn ascend(&mut self) {
+        match self.try_ascend() {
+            Ok(()) => (),
+            Err(TreeError::EmptyTree) => panic!("Tried to move up with an empty tree"),
+            Err(_) => panic!("Tried to move up but current has no father"),
+        }
+    }
+
+    /// Same as [ArenaTree::ascend], but returns an error instead of panicking.
+    pub fn try_ascend(&mut self) -> Result<(), TreeError> {
+        match self.node(self.current_index()?).father {
+            Some(father) => {
+                self.current = Some(father);
+                Ok(())
+            }
+            None => Err(TreeError::NoFather),
+        }
+    }
+
+    /// Return true if current has a father. Note that it will return false and not panic is tree
+    /// is empty.
+    pub fn has_father(&self) -> bool {
+        match self.current {
+            Some(current) => self.node(current).father.is_some(),
+            None => false,
+        }
+    }
+
+    /// Set 'current' to 'tree.root', therefore navigating the tree back to root.
+    pub fn go_to_root(&mut self) {
+        if self.is_empty() {
+            panic!("Tried to move to root on an empty tree");
+        }
+        self.current = self.root;
+    }
+
+    /// Peek at 'current', returning a reference to the element stored in 'current'
+    pub fn peek(&self) -> &T {
+        match self.peek_opt() {
+            Some(el) => el,
+            None => panic!("Tried to peek on an empty tree"),
+        }
+    }
+
+    /// Same as [ArenaTree::peek], but returns None instead of panicking if the tree is empty.
+    pub fn peek_opt(&self) -> Option<&T> {
+        self.current.map(|current| &self.node(current).elem)
+    }
+
+    /// Same as [ArenaTree::peek], but returns a mutable reference instead
+    pub fn peek_mut(&mut self) -> &mut T {
+        if self.is_empty() {
+            panic!("Tried to peek mut on an empty tree");
+        }
+        let current = self.current.unwrap();
+        &mut self.node_mut(current).elem
+    }
+
+    /// Peek on 'current'.childs\[index\]
+    ///
+    /// # Panics
+    /// This method will panic if index >= tree.childs_len()
+    pub fn peek_child(&self, index: usize) -> &T {
+        match self.try_peek_child(index) {
+            Ok(el) => el,
+            Err(TreeError::ChildOutOfRange { index, len }) => panic!(
+                "Tried to call peek_child on child {} but current has only {} childs",
+                index, len
+            ),
+            Err(_) => panic!("Tried to call peek_child on an empty tree"),
+        }
+    }
+
+    /// Same as [ArenaTree::peek_child], but returns an error instead of panicking.
+    pub fn try_peek_child(&self, index: usize) -> Result<&T, TreeError> {
+        let current_node = self.node(self.current_index()?);
+        match current_node.childs.get(index) {
+            Some(&child) => Ok(&self.node(child).elem),
+            None => Err(TreeError::ChildOutOfRange {
+                index,
+                len: current_node.childs.len(),
+            }),
+        }
+    }
+
+    /// Same as [ArenaTree::peek_child], but returns None instead of panicking.
+    pub fn get_child(&self, index: usize) -> Option<&T> {
+        self.try_peek_child(index).ok()
+    }
+
+    /// Same as [ArenaTree::peek_child] but returns a mutable reference.
+    ///
+    /// # Panics
+    /// This method will panic if index >= tree.childs_len()
+    pub fn peek_child_mut(&mut self, index: usize) -> &mut T {
+        if self.is_empty() {
+            panic!("Tried to call peek_child_mut on an empty tree");
+        }
+        let len = self.childs_len();
+        if index >= len {
+            panic!(
+                "Tried to call peek_child_mut on child {} but current has only {} childs",
+                index, len
+            );
+        }
+        let child = self.node(self.current.unwrap()).childs[index];
+        &mut self.node_mut(child).elem
+    }
+
+    /// Returns 'current'.childs.len
+    pub fn childs_len(&self) -> usize {
+        if self.is_empty() {
+            panic!("Tried to call childs_len on an empty tree");
+        }
+        self.node(self.current.unwrap()).childs.len()
+    }
+
+    /// Return an iterator over the elements of current
+    pub fn iter_childs(&self) -> impl Iterator<Item = &T> {
+        if self.is_empty() {
+            panic!("Tried to call iter_childs on an empty tree");
+        }
+        self.node(self.current.unwrap())
+            .childs
+            .iter()
+            .map(|&child| &self.node(child).elem)
+    }
+
+    /// Return the [NodeId] of 'current'.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::ArenaTree;
+    /// let mut tree = ArenaTree::from_element(1);
+    /// tree.push(2);
+    /// tree.navigate_to(0);
+    /// let id = tree.current_id();
+    /// tree.go_to_root();
+    /// tree.navigate_to_id(id).unwrap();
+    /// assert_eq!(tree.peek(), &2);
+    /// ```
+    pub fn current_id(&self) -> NodeId {
+        if self.is_empty() {
+            panic!("Tried to call current_id on an empty tree");
+        }
+        self.id_of(self.current.unwrap())
+    }
+
+    /// Return the [NodeId] of 'root'.
+    pub fn root_id(&self) -> NodeId {
+        if self.is_empty() {
+            panic!("Tried to call root_id on an empty tree");
+        }
+        self.id_of(self.root.unwrap())
+    }
+
+    /// Set 'current' to the node pointed by id. Returns [TreeError::StaleNode] if the node
+    /// has been removed from the tree since id was created.
+    pub fn navigate_to_id(&mut self, id: NodeId) -> Result<(), TreeError> {
+        let index = self.index_of(id).ok_or(TreeError::StaleNode)?;
+        self.current = Some(index);
+        Ok(())
+    }
+
+    /// Return a reference to the element stored in the node pointed by id, or None if id is
+    /// stale.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::ArenaTree;
+    /// let mut tree = ArenaTree::from_element(1);
+    /// tree.push(2);
+    /// tree.navigate_to(0);
+    /// let id = tree.current_id();
+    /// tree.ascend();
+    /// assert_eq!(tree.get(id), Some(&2));
+    /// tree.split(0);
+    /// assert_eq!(tree.get(id), None);
+    /// ```
+    pub fn get(&self, id: NodeId) -> Option<&T> {
+        self.index_of(id).map(|index| &self.node(index).elem)
+    }
+
+    /// Same as [ArenaTree::get], but returns a mutable reference.
+    pub fn get_mut(&mut self, id: NodeId) -> Option<&mut T> {
+        let index = self.index_of(id)?;
+        Some(&mut self.node_mut(index).elem)
+    }
+
+    /// Insert the other tree into 'current'.childs at index. Every node of other is moved into
+    /// this arena.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::ArenaTree;
+    /// let mut tree1 = ArenaTree::from_element(0);
+    /// tree1.push_iter(vec![1, 2, 3]);
+    /// let mut tree2 = ArenaTree::from_element(4);
+    /// tree2.push_iter(vec![5, 6]);
+    /// tree1.join(tree2, 1);
+    /// tree1.navigate_to(1);
+    /// assert_eq!(tree1.iter_childs().collect::<Vec<&i32>>(), vec![&5, &6]);
+    /// ```
+    ///
+    /// # Panics
+    /// This method panic if either of the trees are empty or if index > tree.childs_len()
+    pub fn join(&mut self, mut other: ArenaTree<T>, index: usize) {
+        if self.is_empty() || other.is_empty() {
+            panic!("Tried to call join on an empty tree");
+        }
+        let current = self.current.unwrap();
+        let len = self.childs_len();
+        if index > len {
+            panic!(
+                "Tried to call join with index {} but current has only {} childs",
+                index, len
+            );
+        }
+
+        let other_root = other.root.unwrap();
+        let new_root = self.move_from(&mut other, other_root, current);
+        self.node_mut(current).childs.insert(index, new_root);
+    }
+
+    /// Same as [ArenaTree::join], but returns an error instead of panicking. The error comes with
+    /// other, so that it is not lost.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::{ArenaTree, TreeError};
+    /// let mut tree1 = ArenaTree::from_element(0);
+    /// let tree2 = ArenaTree::from_element(1);
+    /// let (err, tree2) = tree1.try_join(tree2, 1).unwrap_err();
+    /// assert_eq!(err, TreeError::ChildOutOfRange { index: 1, len: 0 });
+    /// assert_eq!(tree2.peek(), &1);
+    /// ```
+    pub fn try_join(
+        &mut self,
+        other: ArenaTree<T>,
+        index: usize,
+    ) -> Result<(), (TreeError, ArenaTree<T>)> {
+        let len = match self.current_index() {
+            Ok(current) => self.node(current).childs.len(),
+            Err(err) => return Err((err, other)),
+        };
+        if other.is_empty() {
+            return Err((TreeError::EmptyTree, other));
+        }
+        if index > len {
+            return Err((TreeError::ChildOutOfRange { index, len }, other));
+        }
+        self.join(other, index);
+        Ok(())
+    }
+
+    /// Move the subtree rooted at other\[index\] into self, as a child of father (without
+    /// registering it in father.childs). Return the new index of the subtree root.
+    fn move_from(&mut self, other: &mut ArenaTree<T>, index: usize, father: usize) -> usize {
+        let mut new_root = None;
+        let mut stack = vec![(index, father)];
+        while let Some((old, new_father)) = stack.pop() {
+            let node = other.dealloc(old);
+            let new = self.alloc(node.elem, Some(new_father));
+            match new_root {
+                None => new_root = Some(new),
+                Some(_) => self.node_mut(new_father).childs.push(new),
+            }
+            for &child in node.childs.iter().rev() {
+                stack.push((child, new));
+            }
+        }
+        new_root.unwrap()
+    }
+
+    /// Remove from 'current' the subtree rooted in 'current'.childs\[index\] and return it as a new
+    /// tree. Every node of the subtree is moved into the new arena, and every [NodeId] pointing
+    /// into the subtree becomes stale.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::ArenaTree;
+    /// let mut tree = ArenaTree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// tree.navigate_to(1);
+    /// tree.push_iter(vec![3, 4]);
+    /// tree.ascend();
+    /// let split_tree = tree.split(1);
+    /// assert_eq!(split_tree.peek(), &2);
+    /// assert_eq!(split_tree.iter_childs().collect::<Vec<&i32>>(), vec![&3, &4]);
+    /// assert_eq!(tree.len(), 2);
+    /// ```
+    ///
+    /// # Panics
+    /// This method will panic if index >= tree.childs_len()
+    pub fn split(&mut self, index: usize) -> ArenaTree<T> {
+        if self.is_empty() {
+            panic!("Tried to call split on an empty tree");
+        }
+        let len = self.childs_len();
+        if index >= len {
+            panic!(
+                "Tried to call split with index {} but current has only {} childs",
+                index, len
+            );
+        }
+
+        let current = self.current.unwrap();
+        let split_root = self.node_mut(current).childs.remove(index);
+        let mut new_tree = ArenaTree::default();
+        let mut stack = vec![(split_root, None)];
+        while let Some((old, new_father)) = stack.pop() {
+            let node = self.dealloc(old);
+            let new = new_tree.alloc(node.elem, new_father);
+            match new_father {
+                None => {
+                    new_tree.root = Some(new);
+                    new_tree.current = Some(new);
+                }
+                Some(father) => new_tree.node_mut(father).childs.push(new),
+            }
+            for &child in node.childs.iter().rev() {
+                stack.push((child, Some(new)));
+            }
+        }
+        new_tree
+    }
+
+    /// Same as [ArenaTree::split], but returns an error instead of panicking.
+    pub fn try_split(&mut self, index: usize) -> Result<ArenaTree<T>, TreeError> {
+        let len = self.node(self.current_index()?).childs.len();
+        if index >= len {
+            return Err(TreeError::ChildOutOfRange { index, len });
+        }
+        Ok(self.split(index))
+    }
+
+    /// Return an [ArenaCursor] pointing at 'current'
+    pub fn cursor(&self) -> ArenaCursor<'_, T> {
+        if self.is_empty() {
+            panic!("Tried to call cursor on an empty tree");
+        }
+        ArenaCursor {
+            tree: self,
+            current: self.current.unwrap(),
+        }
+    }
+
+    /// Return an [ArenaCursor] pointing at 'root'
+    pub fn cursor_root(&self) -> ArenaCursor<'_, T> {
+        if self.is_empty() {
+            panic!("Tried to call cursor_root on an empty tree");
+        }
+        ArenaCursor {
+            tree: self,
+            current: self.root.unwrap(),
+        }
+    }
+
+    /// Return an [ArenaCursorMut] pointing at 'current'
+    pub fn cursor_mut(&mut self) -> ArenaCursorMut<'_, T> {
+        if self.is_empty() {
+            panic!("Tried to call cursor on an empty tree");
+        }
+        let current = self.current.unwrap();
+        ArenaCursorMut {
+            tree: self,
+            current,
+        }
+    }
+
+    /// Return an [ArenaCursorMut] pointing at 'root'
+    pub fn cursor_root_mut(&mut self) -> ArenaCursorMut<'_, T> {
+        if self.is_empty() {
+            panic!("Tried to call cursor_root on an empty tree");
+        }
+        let current = self.root.unwrap();
+        ArenaCursorMut {
+            tree: self,
+            current,
+        }
+    }
+
+    /// Collect the subtree in a depth-first order rooted at 'current' into a vec, and ascend
+    /// 'current'. If 'current' is at 'root', the tree becomes an empty tree. This is the same
+    /// behaviour as [crate::Tree::into_vec].
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::ArenaTree;
+    /// let mut tree = ArenaTree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// tree.navigate_to(0);
+    /// tree.push(3);
+    /// assert_eq!(tree.into_vec(), vec![1, 3]);
+    /// assert_eq!(tree.peek(), &0);
+    /// assert_eq!(tree.into_vec(), vec![0, 2]);
+    /// assert!(tree.is_empty());
+    /// ```
+    pub fn into_vec(&mut self) -> Vec<T> {
+        if self.is_empty() {
+            panic!("Tried to call into_vec on an empty tree");
+        }
+
+        let current = self.current.unwrap();
+        match self.node(current).father {
+            Some(father) => {
+                self.node_mut(father)
+                    .childs
+                    .retain(|&child| child != current);
+                self.current = Some(father);
+            }
+            None => {
+                self.root = None;
+                self.current = None;
+            }
+        }
+
+        let mut container = Vec::new();
+        let mut stack = vec![current];
+        while let Some(index) = stack.pop() {
+            let node = self.dealloc(index);
+            stack.extend(node.childs.iter().rev());
+            container.push(node.elem);
+        }
+        container
+    }
+
+    /// Iterate over references of element stored in the subtree rooted at 'current' in a
+    /// depth-first way. Note that this method will not panic if called on an empty tree.
+    pub fn iter(&self) -> ArenaIter<'_, T> {
+        ArenaIter {
+            tree: self,
+            stack: self.current.into_iter().collect(),
+        }
+    }
+
+    /// Iterate over mutable references of element stored in the subtree rooted at 'current' in a
+    /// depth-first way. Note that this method will not panic if called on an empty tree.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::ArenaTree;
+    /// let mut tree = ArenaTree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// for el in tree.iter_mut() {
+    ///     *el *= 10;
+    /// }
+    /// assert_eq!(tree.iter().collect::<Vec<&i32>>(), vec![&0, &10, &20]);
+    /// ```
+    pub fn iter_mut(&mut self) -> ArenaIterMut<'_, T> {
+        let stack = self.current.into_iter().collect();
+        ArenaIterMut {
+            slots: self.slots.as_mut_ptr(),
+            stack,
+            _boo: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for ArenaTree<T>
+where
+    T: Clone,
+{
+    /// Clone the tree into a new arena, so the [NodeId]s of self are not valid in the clone.
+    fn clone(&self) -> Self {
+        ArenaTree {
+            arena: NEXT_ARENA.fetch_add(1, Ordering::Relaxed),
+            slots: self.slots.clone(),
+            free: self.free.clone(),
+            root: self.root,
+            current: self.current,
+            len: self.len,
+        }
+    }
+}
+
+impl<T> Default for ArenaTree<T> {
+    fn default() -> Self {
+        ArenaTree {
+            arena: NEXT_ARENA.fetch_add(1, Ordering::Relaxed),
+            slots: Vec::new(),
+            free: Vec::new(),
+            root: None,
+            current: None,
+            len: 0,
+        }
+    }
+}
+
+impl<T> From<ArenaTree<T>> for Vec<T> {
+    fn from(mut tree: ArenaTree<T>) -> Vec<T> {
+        tree.go_to_root();
+        tree.into_vec()
+    }
+}
+
+/// Equivalent of [crate::Cursor] for [ArenaTree]
+///
+/// # Examples
+/// ```
+/// # use libtree::ArenaTree;
+/// let mut tree = ArenaTree::from_element(10);
+/// tree.push_iter(vec![1, 2, 3]);
+/// let cursor1 = tree.cursor();
+/// let mut cursor2 = tree.cursor_root();
+/// cursor2.navigate_to(2);
+/// assert_eq!(cursor1.peek(), &10);
+/// assert_eq!(cursor2.peek(), &3);
+/// ```
+pub struct ArenaCursor<'a, T> {
+    tree: &'a ArenaTree<T>,
+    current: usize,
+}
+
+impl<'a, T> Clone for ArenaCursor<'a, T> {
+    fn clone(&self) -> Self {
+        ArenaCursor {
+            tree: self.tree,
+            current: self.current,
+        }
+    }
+}
+
+impl<'a, T> ArenaCursor<'a, T> {
+    /// Peek at 'current', returning a reference to the element stored in 'current'.
+    pub fn peek(&self) -> &'a T {
+        &self.tree.node(self.current).elem
+    }
+
+    /// Peek at 'current'.childs\[index\], returning a reference to the element stored.
+    ///
+    /// # Panics
+    /// This method will panic if index >= self.childs_len
+    pub fn peek_child(&self, index: usize) -> &'a T {
+        match self.get_child(index) {
+            Some(el) => el,
+            None => panic!(
+                "Tried to peek child on child {} but current has only {} childs",
+                index,
+                self.childs_len()
+            ),
+        }
+    }
+
+    /// Same as [ArenaCursor::peek_child], but returns None instead of panicking.
+    pub fn get_child(&self, index: usize) -> Option<&'a T> {
+        let tree = self.tree;
+        tree.node(self.current)
+            .childs
+            .get(index)
+            .map(|&child| &tree.node(child).elem)
+    }
+
+    /// Set 'current' to 'current'.childs\[index\], therefore navigating to this child
+    ///
+    /// # Panics
+    /// This method will panic if index >= self.childs_len
+    pub fn navigate_to(&mut self, index: usize) {
+        if self.try_navigate_to(index).is_err() {
+            panic!(
+                "Tried to navigate to child {} but current has only {} childs",
+                index,
+                self.childs_len()
+            );
+        }
+    }
+
+    /// Same as [ArenaCursor::navigate_to], but returns an error instead of panicking.
+    pub fn try_navigate_to(&mut self, index: usize) -> Result<(), TreeError> {
+        match self.tree.node(self.current).childs.get(index) {
+            Some(&child) => {
+                self.current = child;
+                Ok(())
+            }
+            None => Err(TreeError::ChildOutOfRange {
+                index,
+                len: self.childs_len(),
+            }),
+        }
+    }
+
+    /// Set 'current' to 'current'.father, therefore navigating up.
+    ///
+    /// # Panics
+    /// This method will panic if 'current' has no father
+    pub fn ascend(&mut self) {
+        if self.try_ascend().is_err() {
+            panic!("Tried to call ascend but current has no father");
+        }
+    }
+
+    /// Same as [ArenaCursor::ascend], but returns [TreeError::NoFather] instead of panicking.
+    pub fn try_ascend(&mut self) -> Result<(), TreeError> {
+        self.current = self
+            .tree
+            .node(self.current)
+            .father
+            .ok_or(TreeError::NoFather)?;
+        Ok(())
+    }
+
+    /// Return true if 'current' has a father.
+    pub fn has_father(&self) -> bool {
+        self.tree.node(self.current).father.is_some()
+    }
+
+    /// Return the number of childrens of current.
+    pub fn childs_len(&self) -> usize {
+        self.tree.node(self.current).childs.len()
+    }
+
+    /// Return the [NodeId] of 'current'.
+    pub fn id(&self) -> NodeId {
+        self.tree.id_of(self.current)
+    }
+
+    /// Return an Iterator over the elements stored in 'current'.childs
+    pub fn iter_childs(&self) -> impl Iterator<Item = &'a T> {
+        let tree = self.tree;
+        tree.node(self.current)
+            .childs
+            .iter()
+            .map(|&child| &tree.node(child).elem)
+    }
+
+    /// Iterate over references of element stored in the subtree rooted at 'current' in a
+    /// depth-first way.
+    pub fn iter(&self) -> ArenaIter<'a, T> {
+        ArenaIter {
+            tree: self.tree,
+            stack: vec![self.current],
+        }
+    }
+}
+
+/// Equivalent of [crate::CursorMut] for [ArenaTree]
+///
+/// # Examples
+/// ```
+/// # use libtree::ArenaTree;
+/// let mut tree = ArenaTree::from_element(10);
+/// let mut cursor = tree.cursor_mut();
+/// cursor.push(1);
+/// cursor.navigate_to(0);
+/// *cursor.peek_mut() += 1;
+/// assert_eq!(tree.into_vec(), vec![10, 2]);
+/// ```
+pub struct ArenaCursorMut<'a, T> {
+    tree: &'a mut ArenaTree<T>,
+    current: usize,
+}
+
+impl<'a, T> ArenaCursorMut<'a, T> {
+    /// Peek at 'current', returning a reference to the element stored in 'current'.
+    pub fn peek(&self) -> &T {
+        &self.tree.node(self.current).elem
+    }
+
+    /// Peek at 'current', returning a mutable reference to the element stored in 'current'.
+    pub fn peek_mut(&mut self) -> &mut T {
+        &mut self.tree.node_mut(self.current).elem
+    }
+
+    /// Peek at 'current'.childs\[index\], returning a reference to the element stored.
+    ///
+    /// # Panics
+    /// This method will panic if index >= self.childs_len
+    pub fn peek_child(&self, index: usize) -> &T {
+        match self.get_child(index) {
+            Some(el) => el,
+            None => panic!(
+                "Tried to peek child on child {} but current has only {} childs",
+                index,
+                self.childs_len()
+            ),
+        }
+    }
+
+    /// Same as [ArenaCursorMut::peek_child], but returns None instead of panicking.
+    pub fn get_child(&self, index: usize) -> Option<&T> {
+        self.tree
+            .node(self.current)
+            .childs
+            .get(index)
+            .map(|&child| &self.tree.node(child).elem)
+    }
+
+    /// Set 'current' to 'current'.childs\[index\], therefore navigating to this child
+    ///
+    /// # Panics
+    /// This method will panic if index >= self.childs_len
+    pub fn navigate_to(&mut self, index: usize) {
+        if self.try_navigate_to(index).is_err() {
+            panic!(
+                "Tried to navigate to child {} but current has only {} childs",
+                index,
+                self.childs_len()
+            );
+        }
+    }
+
+    /// Same as [ArenaCursorMut::navigate_to], but returns an error instead of panicking.
+    pub fn try_navigate_to(&mut self, index: usize) -> Result<(), TreeError> {
+        match self.tree.node(self.current).childs.get(index) {
+            Some(&child) => {
+                self.current = child;
+                Ok(())
+            }
+            None => Err(TreeError::ChildOutOfRange {
+                index,
+                len: self.childs_len(),
+            }),
+        }
+    }
+
+    /// Set 'current' to 'current'.father, therefore navigating up.
+    ///
+    /// # Panics
+    /// This method will panic if 'current' has no father
+    pub fn ascend(&mut self) {
+        if self.try_ascend().is_err() {
+            panic!("Tried to call ascend but current has no father");
+        }
+    }
+
+    /// Same as [ArenaCursorMut::ascend], but returns [TreeError::NoFather] instead of panicking.
+    pub fn try_ascend(&mut self) -> Result<(), TreeError> {
+        self.current = self
+            .tree
+            .node(self.current)
+            .father
+            .ok_or(TreeError::NoFather)?;
+        Ok(())
+    }
+
+    /// Return true if 'current' has a father.
+    pub fn has_father(&self) -> bool {
+        self.tree.node(self.current).father.is_some()
+    }
+
+    /// Return the number of childrens of current.
+    pub fn childs_len(&self) -> usize {
+        self.tree.node(self.current).childs.len()
+    }
+
+    /// Return the [NodeId] of 'current'.
+    pub fn id(&self) -> NodeId {
+        self.tree.id_of(self.current)
+    }
+
+    /// Push el to 'current'.child as a new node in the tree.
+    pub fn push(&mut self, el: T) {
+        let child = self.tree.alloc(el, Some(self.current));
+        self.tree.node_mut(self.current).childs.push(child);
+    }
+
+    /// Convenient method to push the elements of an iterator into the tree.
+    pub fn push_iter<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for el in iter.into_iter() {
+            self.push(el);
+        }
+    }
+
+    /// Iterate over references of element stored in the subtree rooted at 'current' in a
+    /// depth-first way.
+    pub fn iter(&self) -> ArenaIter<'_, T> {
+        ArenaIter {
+            tree: self.tree,
+            stack: vec![self.current],
+        }
+    }
+
+    /// Same as [ArenaCursorMut::iter], but returns mutable references instead
+    pub fn iter_mut(&mut self) -> ArenaIterMut<'_, T> {
+        ArenaIterMut {
+            slots: self.tree.slots.as_mut_ptr(),
+            stack: vec![self.current],
+            _boo: PhantomData,
+        }
+    }
+}
+
+/// Depth-first iterator over the references of a subtree of an [ArenaTree]
+pub struct ArenaIter<'a, T> {
+    tree: &'a ArenaTree<T>,
+    stack: Vec<usize>,
+}
+
+impl<'a, T> Iterator for ArenaIter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.tree.node(self.stack.pop()?);
+        self.stack.extend(node.childs.iter().rev());
+        Some(&node.elem)
+    }
+}
+
+/// Depth-first iterator over the mutable references of a subtree of an [ArenaTree]
+pub struct ArenaIterMut<'a, T> {
+    slots: *mut Slot<T>,
+    stack: Vec<usize>,
+    _boo: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Iterator for ArenaIterMut<'a, T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.stack.pop()?;
+        // Every node of the subtree is visited exactly once, so the mutable references returned
+        // never alias.
+        let node = unsafe { (*self.slots.add(index)).node.as_mut().unwrap() };
+        self.stack.extend(node.childs.iter().rev());
+        Some(&mut node.elem)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sample() -> ArenaTree<i32> {
+        let mut tree = ArenaTree::from_element(0);
+        tree.push_iter(vec![1, 2, 3]);
+        tree.navigate_to(1);
+        tree.push_iter(vec![9, 8]);
+        tree.ascend();
+        tree.navigate_to(0);
+        tree.push_iter(vec![9, 10]);
+        tree.navigate_to(0);
+        tree.push(15);
+        tree.go_to_root();
+        tree
+    }
+
+    #[test]
+    fn iter() {
+        let mut tree = sample();
+        assert_eq!(
+            tree.iter().collect::<Vec<&i32>>(),
+            vec![&0, &1, &9, &15, &10, &2, &9, &8, &3]
+        );
+        tree.navigate_to(1);
+        assert_eq!(tree.iter().collect::<Vec<&i32>>(), vec![&2, &9, &8]);
+        assert_eq!(tree.cursor_root().iter().count(), 9);
+    }
+
+    #[test]
+    fn split_and_join_reuse_slots() {
+        let mut tree = sample();
+        let split_tree = tree.split(0);
+        assert_eq!(tree.len(), 5);
+        assert_eq!(split_tree.len(), 4);
+        assert_eq!(
+            split_tree.iter().collect::<Vec<&i32>>(),
+            vec![&1, &9, &15, &10]
+        );
+
+        tree.join(split_tree, 2);
+        assert_eq!(tree.len(), 9);
+        assert_eq!(tree.slots.len(), 9);
+        assert_eq!(
+            tree.iter().collect::<Vec<&i32>>(),
+            vec![&0, &2, &9, &8, &3, &1, &9, &15, &10]
+        );
+    }
+
+    #[test]
+    fn stale_node_id() {
+        let mut tree = sample();
+        tree.navigate_to(0);
+        let id = tree.current_id();
+        tree.ascend();
+        let _ = tree.split(0);
+        tree.push(42);
+        assert_eq!(tree.get(id), None);
+        assert_eq!(tree.navigate_to_id(id), Err(TreeError::StaleNode));
+        assert_eq!(tree.peek(), &0);
+
+        // slots of the split tree restart at generation 0, but its ids are its own
+        let mut tree = sample();
+        let mut split_tree = tree.split(0);
+        assert_eq!(split_tree.get(tree.root_id()), None);
+        assert_eq!(tree.get(split_tree.root_id()), None);
+        assert_eq!(
+            split_tree.navigate_to_id(tree.current_id()),
+            Err(TreeError::StaleNode)
+        );
+        let clone = tree.clone();
+        assert_eq!(clone.get(tree.root_id()), None);
+        assert_eq!(clone.get(clone.root_id()), Some(&0));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Tried to move to children 3 of current node, but current node has only 3 childs"
+    )]
+    fn navigate_to_panic() {
+        let mut tree = sample();
+        tree.navigate_to(3);
+    }
+
+    #[test]
+    fn cursor_mut() {
+        let mut tree = sample();
+        let mut cursor = tree.cursor_mut();
+        cursor.navigate_to(2);
+        cursor.push(4);
+        for el in cursor.iter_mut() {
+            *el *= 2;
+        }
+        assert_eq!(Vec::from(tree), vec![0, 1, 9, 15, 10, 2, 9, 8, 6, 8]);
+    }
+}
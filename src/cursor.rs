@@ -1,8 +1,15 @@
+use crate::iter::{
+    BfsIterator, BfsIteratorMut, LevelIterator, PostOrderIterator, PostOrderIteratorMut,
+    ScanIterator,
+};
+use crate::stats::{_depth, _height, _leaf_count, _subtree_len};
 use crate::tree::{
-    ChildIterator, ChildIteratorMut, ChildLink, LazyTreeIterator, LazyTreeIteratorMut, Node,
-    _iter_rec, _iter_rec_mut,
+    ChildIterator, ChildIteratorMut, ChildLink, LazyTreeIterator, LazyTreeIteratorMut, Link, Node,
+    _ascend_n, _child_index, _drain_filter, _fold, _fold_to_tree, _follow_path, _iter_dfs,
+    _iter_dfs_mut, _path, _remove_child_splice,
 };
-use std::{collections::LinkedList, marker::PhantomData, ptr::NonNull};
+use crate::{Dot, NodeHandle, Render, Tree, TreeError, TreeStats};
+use std::{cell::OnceCell, collections::LinkedList, fmt, io, marker::PhantomData, ptr::NonNull};
 
 /// Equivalent of immutable reference for [crate::Tree]
 ///
@@ -69,8 +76,29 @@ pub struct Cursor<'a, T> {
 /// // Not very pratical if we, for exemple, want to build an iterator on the whole tree from a
 /// // CursorMut.
 /// ```
+///
+/// # Removing nodes
+/// A CursorMut can remove nodes below its own position (see [CursorMut::remove_child_splice] and
+/// [CursorMut::prune_where]), even when it navigated above 'current' of its tree. It keeps track
+/// of 'current' of the tree, so that 'current' never sits on a removed node: it moves to the
+/// closest node left in the tree instead.
+///
+/// ```
+/// # use libtree::Tree;
+/// let mut tree = Tree::from_element(0);
+/// tree.push(1);
+/// tree.navigate_to(0);
+/// tree.push(2);
+/// tree.navigate_to(0);
+/// let mut cursor = tree.cursor_mut();
+/// cursor.ascend_n(2);
+/// cursor.prune_where(|el| *el == 1);
+/// assert_eq!(tree.peek(), &0);
+/// ```
 pub struct CursorMut<'a, T> {
     pub(crate) current: ChildLink<T>,
+    /// 'current' of the tree the cursor comes from, moved away from the nodes the cursor removes.
+    pub(crate) tree_current: NonNull<Link<T>>,
     pub(crate) _boo: PhantomData<&'a T>,
 }
 
@@ -107,7 +135,39 @@ impl<'a, T> Cursor<'a, T> {
             );
         }
 
-        unsafe { &(*(*self.current.as_ptr()).childs[index].as_ptr()).elem }
+        unsafe { &(*(&(*self.current.as_ptr()).childs)[index].as_ptr()).elem }
+    }
+
+    /// Same as [Cursor::peek_child], but returns an error instead of panicking.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::{Tree, TreeError};
+    /// let mut tree = Tree::from_element(10);
+    /// tree.push(5);
+    /// let cursor = tree.cursor();
+    /// assert_eq!(cursor.try_peek_child(0), Ok(&5));
+    /// assert_eq!(
+    ///     cursor.try_peek_child(1),
+    ///     Err(TreeError::ChildOutOfRange { index: 1, len: 1 })
+    /// );
+    /// ```
+    pub fn try_peek_child(&self, index: usize) -> Result<&'a T, TreeError> {
+        unsafe {
+            let childs = &(*self.current.as_ptr()).childs;
+            match childs.get(index) {
+                Some(child) => Ok(&(*child.as_ptr()).elem),
+                None => Err(TreeError::ChildOutOfRange {
+                    index,
+                    len: self.childs_len(),
+                }),
+            }
+        }
+    }
+
+    /// Same as [Cursor::peek_child], but returns None instead of panicking.
+    pub fn get_child(&self, index: usize) -> Option<&'a T> {
+        self.try_peek_child(index).ok()
     }
 
     /// Set 'current' to 'current'.childs\[index\], therefore navigating to this child
@@ -134,7 +194,38 @@ impl<'a, T> Cursor<'a, T> {
         }
 
         unsafe {
-            self.current = (*self.current.as_ptr()).childs[index];
+            self.current = (&(*self.current.as_ptr()).childs)[index];
+        }
+    }
+
+    /// Same as [Cursor::navigate_to], but returns an error instead of panicking.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::{Tree, TreeError};
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push(1);
+    /// let mut cursor = tree.cursor();
+    /// assert_eq!(
+    ///     cursor.try_navigate_to(2),
+    ///     Err(TreeError::ChildOutOfRange { index: 2, len: 1 })
+    /// );
+    /// assert_eq!(cursor.try_navigate_to(0), Ok(()));
+    /// assert_eq!(cursor.peek(), &1);
+    /// ```
+    pub fn try_navigate_to(&mut self, index: usize) -> Result<(), TreeError> {
+        unsafe {
+            let childs = &(*self.current.as_ptr()).childs;
+            match childs.get(index) {
+                Some(child) => {
+                    self.current = *child;
+                    Ok(())
+                }
+                None => Err(TreeError::ChildOutOfRange {
+                    index,
+                    len: self.childs_len(),
+                }),
+            }
         }
     }
 
@@ -163,6 +254,25 @@ impl<'a, T> Cursor<'a, T> {
         }
     }
 
+    /// Same as [Cursor::ascend], but returns [TreeError::NoFather] instead of panicking.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::{Tree, TreeError};
+    /// let mut tree = Tree::from_element(0);
+    /// let mut cursor = tree.cursor();
+    /// assert_eq!(cursor.try_ascend(), Err(TreeError::NoFather));
+    /// ```
+    pub fn try_ascend(&mut self) -> Result<(), TreeError> {
+        match unsafe { (*self.current.as_ptr()).father } {
+            Some(father) => {
+                self.current = father;
+                Ok(())
+            }
+            None => Err(TreeError::NoFather),
+        }
+    }
+
     /// Return true if 'current' has a father.
     ///
     /// # Examples
@@ -180,6 +290,80 @@ impl<'a, T> Cursor<'a, T> {
         unsafe { (*self.current.as_ptr()).father.is_some() }
     }
 
+    /// Return the position of 'current' in the childs of its father, or None if 'current' has no
+    /// father.
+    pub fn child_index(&self) -> Option<usize> {
+        _child_index(self.current)
+    }
+
+    /// Return the path from the root of the tree to 'current', see [crate::Tree::path].
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// tree.navigate_to(1);
+    /// tree.push(3);
+    /// tree.navigate_to(0);
+    /// let cursor = tree.cursor();
+    /// assert_eq!(cursor.path(), vec![1, 0]);
+    /// ```
+    pub fn path(&self) -> Vec<usize> {
+        _path(self.current)
+    }
+
+    /// Navigate 'current' successively to each child index of path, starting from 'current'.
+    ///
+    /// # Panics
+    /// This method will panic if one of the indices is out of range. In this case 'current' is
+    /// left unchanged.
+    pub fn navigate_path(&mut self, path: &[usize]) {
+        if let Err(err) = self.try_navigate_path(path) {
+            panic!("Tried to navigate along path {:?}, but {}", path, err);
+        }
+    }
+
+    /// Same as [Cursor::navigate_path], but returns an error instead of panicking.
+    pub fn try_navigate_path(&mut self, path: &[usize]) -> Result<(), TreeError> {
+        self.current = _follow_path(self.current, path)?;
+        Ok(())
+    }
+
+    /// Ascend 'current' n times.
+    ///
+    /// # Panics
+    /// This method will panic if 'current' has less than n ancestors. In this case 'current' is
+    /// left unchanged.
+    pub fn ascend_n(&mut self, n: usize) {
+        if let Err(err) = self.try_ascend_n(n) {
+            panic!("Tried to ascend {} times, but {}", n, err);
+        }
+    }
+
+    /// Same as [Cursor::ascend_n], but returns an error instead of panicking.
+    pub fn try_ascend_n(&mut self, n: usize) -> Result<(), TreeError> {
+        self.current = _ascend_n(self.current, n)?;
+        Ok(())
+    }
+
+    /// Return a [NodeHandle] to 'current', see [crate::Tree::handle].
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push(1);
+    /// let mut cursor = tree.cursor();
+    /// cursor.navigate_to(0);
+    /// let handle = cursor.handle();
+    /// tree.navigate_to_handle(&handle).unwrap();
+    /// assert_eq!(tree.peek(), &1);
+    /// ```
+    pub fn handle(&self) -> NodeHandle<T> {
+        NodeHandle::new(self.current)
+    }
+
     /// Return the number of childrens of current.
     ///
     /// # Examples
@@ -194,6 +378,124 @@ impl<'a, T> Cursor<'a, T> {
         unsafe { (*self.current.as_ptr()).childs.len() }
     }
 
+    /// Return the number of nodes in the subtree rooted at 'current' (including 'current').
+    pub fn subtree_len(&self) -> usize {
+        _subtree_len(Some(self.current))
+    }
+
+    /// Return the distance between the root of the tree and 'current'.
+    pub fn depth(&self) -> usize {
+        _depth(Some(self.current))
+    }
+
+    /// Return the length of the longest path from 'current' to a leaf of its subtree.
+    pub fn height(&self) -> usize {
+        _height(Some(self.current))
+    }
+
+    /// Return the number of nodes without childs in the subtree rooted at 'current'.
+    pub fn leaf_count(&self) -> usize {
+        _leaf_count(Some(self.current))
+    }
+
+    /// Return a [TreeStats] report on the shape of the subtree rooted at 'current'.
+    pub fn stats(&self) -> TreeStats {
+        TreeStats::new(Some(self.current))
+    }
+
+    /// Return a [Render] drawing the subtree rooted at 'current'. See [Render] for the available
+    /// options. [Render::highlight_current] marks 'current' of the cursor, the first drawn node.
+    pub fn render(&self) -> Render<'a, T> {
+        Render::new(Some(self.current), Some(self.current))
+    }
+
+    /// Fold the subtree rooted at 'current' from the leaves up: f is called on every node with
+    /// its element and the results of its childs (in order), so a leaf gets an empty Vec, and the
+    /// result of 'current' is returned. This is done without recursion, so it works on very
+    /// deep trees.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let tree: Tree<i32> = "(0 (0 3 5) (0 2 9))".parse().unwrap();
+    /// let leaves = tree.cursor().fold(|_, childs: Vec<usize>| {
+    ///     if childs.is_empty() {
+    ///         1
+    ///     } else {
+    ///         childs.iter().sum()
+    ///     }
+    /// });
+    /// assert_eq!(leaves, 4);
+    /// let best = tree
+    ///     .cursor()
+    ///     .fold(|elem, childs: Vec<i32>| childs.into_iter().max().unwrap_or(*elem));
+    /// assert_eq!(best, 9);
+    /// ```
+    pub fn fold<R, F>(&self, f: F) -> R
+    where
+        F: FnMut(&T, Vec<R>) -> R,
+    {
+        _fold(self.current, f)
+    }
+
+    /// Same as [Cursor::fold], but write the result of every node in a new tree of the same
+    /// shape as the subtree rooted at 'current'. f gets references to the results of the childs,
+    /// which are kept in the new tree. 'current' and 'root' of the new tree are the result of
+    /// 'current'.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let tree: Tree<i32> = "(1 (2 3 4) 5)".parse().unwrap();
+    /// let sums = tree.cursor().fold_to_tree(|elem, childs: Vec<&i32>| {
+    ///     elem + childs.into_iter().sum::<i32>()
+    /// });
+    /// assert_eq!(sums.to_sexpr(), "(15 (9 3 4) 5)");
+    /// ```
+    pub fn fold_to_tree<R, F>(&self, f: F) -> Tree<R>
+    where
+        F: FnMut(&T, Vec<&R>) -> R,
+    {
+        _fold_to_tree(self.current, f)
+    }
+
+    /// Iterate lazily over the subtree rooted at 'current' with the value accumulated along the
+    /// path from 'current', see [crate::Tree::scan_iter].
+    pub fn scan_iter<Acc, F>(&self, init: Acc, f: F) -> ScanIterator<'a, T, Acc, F>
+    where
+        Acc: Clone,
+        F: FnMut(&Acc, &T) -> Acc,
+    {
+        ScanIterator::new(Some(self.current), init, f)
+    }
+
+    /// Write the subtree rooted at 'current' as a Graphviz digraph to writer, see
+    /// [crate::Tree::to_dot].
+    pub fn to_dot<W: io::Write>(&self, writer: W) -> io::Result<()>
+    where
+        T: fmt::Display,
+    {
+        self.dot().write_to(writer)
+    }
+
+    /// Return a [Dot] exporter for the subtree rooted at 'current', labelling the nodes with the
+    /// [fmt::Display] of their elements. No node is marked as 'current'.
+    pub fn dot(&self) -> Dot<'a, T>
+    where
+        T: fmt::Display,
+    {
+        Dot::new(Some(self.current), None, |elem: &T| elem.to_string())
+    }
+
+    /// Return a [Dot] exporter for the subtree rooted at 'current', labelling the nodes with
+    /// label.
+    pub fn dot_with<F>(&self, label: F) -> Dot<'a, T>
+    where
+        F: Fn(&T) -> String + 'a,
+    {
+        Dot::new(Some(self.current), None, label)
+    }
+
     /// Return an Iterator over the elements stored in 'current'.childs
     ///
     /// # Examples
@@ -228,7 +530,7 @@ impl<'a, T> Cursor<'a, T> {
     /// assert_eq!(tree.cursor().iter().collect::<Vec<&i32>>(), vec![&2, &4]);
     pub fn iter(&self) -> impl Iterator<Item = &'a T> {
         let mut container = Vec::new();
-        _iter_rec(self.current, &mut container);
+        _iter_dfs(self.current, &mut container);
         container.into_iter()
     }
 
@@ -269,6 +571,48 @@ impl<'a, T> Cursor<'a, T> {
             _boo: PhantomData,
         }
     }
+
+    /// Iterate over the subtree rooted at 'current' in a lazy breadth-first way (level by level,
+    /// from left to right), returning references to the elements stored in the subtree.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// tree.navigate_to(0);
+    /// tree.push_iter(vec![3, 4]);
+    /// tree.ascend();
+    /// let cursor = tree.cursor();
+    /// assert_eq!(cursor.iter_bfs().collect::<Vec<&i32>>(), vec![&0, &1, &2, &3, &4]);
+    /// ```
+    pub fn iter_bfs(&self) -> BfsIterator<'a, T> {
+        BfsIterator::new(Some(self.current))
+    }
+
+    /// Iterate over the levels of the subtree rooted at 'current', yielding a Vec with the
+    /// references of every node at the same depth ('current' being depth 0).
+    pub fn iter_levels(&self) -> LevelIterator<'a, T> {
+        LevelIterator::new(Some(self.current))
+    }
+
+    /// Iterate over the subtree rooted at 'current' in a lazy post-order way, meaning that the
+    /// childs of a node are always returned before it (and so 'current' is returned last).
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// tree.navigate_to(0);
+    /// tree.push_iter(vec![3, 4]);
+    /// tree.ascend();
+    /// let cursor = tree.cursor();
+    /// assert_eq!(cursor.iter_postorder().collect::<Vec<&i32>>(), vec![&3, &4, &1, &2, &0]);
+    /// ```
+    pub fn iter_postorder(&self) -> PostOrderIterator<'a, T> {
+        PostOrderIterator::new(Some(self.current))
+    }
 }
 
 impl<'a, T> CursorMut<'a, T> {
@@ -317,7 +661,39 @@ impl<'a, T> CursorMut<'a, T> {
             );
         }
 
-        unsafe { &(*(*self.current.as_ptr()).childs[index].as_ptr()).elem }
+        unsafe { &(*(&(*self.current.as_ptr()).childs)[index].as_ptr()).elem }
+    }
+
+    /// Same as [CursorMut::peek_child], but returns an error instead of panicking.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::{Tree, TreeError};
+    /// let mut tree = Tree::from_element(10);
+    /// tree.push(5);
+    /// let cursor = tree.cursor_mut();
+    /// assert_eq!(cursor.try_peek_child(0), Ok(&5));
+    /// assert_eq!(
+    ///     cursor.try_peek_child(1),
+    ///     Err(TreeError::ChildOutOfRange { index: 1, len: 1 })
+    /// );
+    /// ```
+    pub fn try_peek_child(&self, index: usize) -> Result<&'a T, TreeError> {
+        unsafe {
+            let childs = &(*self.current.as_ptr()).childs;
+            match childs.get(index) {
+                Some(child) => Ok(&(*child.as_ptr()).elem),
+                None => Err(TreeError::ChildOutOfRange {
+                    index,
+                    len: self.childs_len(),
+                }),
+            }
+        }
+    }
+
+    /// Same as [CursorMut::peek_child], but returns None instead of panicking.
+    pub fn get_child(&self, index: usize) -> Option<&'a T> {
+        self.try_peek_child(index).ok()
     }
 
     /// Peek at 'current'.childs\[index\], returning a mutable reference to the element stored.
@@ -339,7 +715,26 @@ impl<'a, T> CursorMut<'a, T> {
             );
         }
 
-        unsafe { &mut (*(*self.current.as_ptr()).childs[index].as_ptr()).elem }
+        unsafe { &mut (*(&(*self.current.as_ptr()).childs)[index].as_ptr()).elem }
+    }
+
+    /// Same as [CursorMut::peek_child_mut], but returns an error instead of panicking.
+    pub fn try_peek_child_mut(&mut self, index: usize) -> Result<&mut T, TreeError> {
+        unsafe {
+            let childs = &(*self.current.as_ptr()).childs;
+            match childs.get(index) {
+                Some(child) => Ok(&mut (*child.as_ptr()).elem),
+                None => Err(TreeError::ChildOutOfRange {
+                    index,
+                    len: self.childs_len(),
+                }),
+            }
+        }
+    }
+
+    /// Same as [CursorMut::peek_child_mut], but returns None instead of panicking.
+    pub fn get_child_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.try_peek_child_mut(index).ok()
     }
 
     /// Set 'current' to 'current'.childs\[index\], therefore navigating to this child
@@ -366,7 +761,38 @@ impl<'a, T> CursorMut<'a, T> {
         }
 
         unsafe {
-            self.current = (*self.current.as_ptr()).childs[index];
+            self.current = (&(*self.current.as_ptr()).childs)[index];
+        }
+    }
+
+    /// Same as [CursorMut::navigate_to], but returns an error instead of panicking.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::{Tree, TreeError};
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push(1);
+    /// let mut cursor = tree.cursor_mut();
+    /// assert_eq!(
+    ///     cursor.try_navigate_to(2),
+    ///     Err(TreeError::ChildOutOfRange { index: 2, len: 1 })
+    /// );
+    /// assert_eq!(cursor.try_navigate_to(0), Ok(()));
+    /// assert_eq!(cursor.peek(), &1);
+    /// ```
+    pub fn try_navigate_to(&mut self, index: usize) -> Result<(), TreeError> {
+        unsafe {
+            let childs = &(*self.current.as_ptr()).childs;
+            match childs.get(index) {
+                Some(child) => {
+                    self.current = *child;
+                    Ok(())
+                }
+                None => Err(TreeError::ChildOutOfRange {
+                    index,
+                    len: self.childs_len(),
+                }),
+            }
         }
     }
 
@@ -395,6 +821,25 @@ impl<'a, T> CursorMut<'a, T> {
         }
     }
 
+    /// Same as [CursorMut::ascend], but returns [TreeError::NoFather] instead of panicking.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::{Tree, TreeError};
+    /// let mut tree = Tree::from_element(0);
+    /// let mut cursor = tree.cursor_mut();
+    /// assert_eq!(cursor.try_ascend(), Err(TreeError::NoFather));
+    /// ```
+    pub fn try_ascend(&mut self) -> Result<(), TreeError> {
+        match unsafe { (*self.current.as_ptr()).father } {
+            Some(father) => {
+                self.current = father;
+                Ok(())
+            }
+            None => Err(TreeError::NoFather),
+        }
+    }
+
     /// Return true if 'current' has a father.
     ///
     /// # Examples
@@ -412,6 +857,80 @@ impl<'a, T> CursorMut<'a, T> {
         unsafe { (*self.current.as_ptr()).father.is_some() }
     }
 
+    /// Return the position of 'current' in the childs of its father, or None if 'current' has no
+    /// father.
+    pub fn child_index(&self) -> Option<usize> {
+        _child_index(self.current)
+    }
+
+    /// Return the path from the root of the tree to 'current', see [crate::Tree::path].
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// tree.navigate_to(1);
+    /// tree.push(3);
+    /// tree.navigate_to(0);
+    /// let cursor = tree.cursor_mut();
+    /// assert_eq!(cursor.path(), vec![1, 0]);
+    /// ```
+    pub fn path(&self) -> Vec<usize> {
+        _path(self.current)
+    }
+
+    /// Navigate 'current' successively to each child index of path, starting from 'current'.
+    ///
+    /// # Panics
+    /// This method will panic if one of the indices is out of range. In this case 'current' is
+    /// left unchanged.
+    pub fn navigate_path(&mut self, path: &[usize]) {
+        if let Err(err) = self.try_navigate_path(path) {
+            panic!("Tried to navigate along path {:?}, but {}", path, err);
+        }
+    }
+
+    /// Same as [CursorMut::navigate_path], but returns an error instead of panicking.
+    pub fn try_navigate_path(&mut self, path: &[usize]) -> Result<(), TreeError> {
+        self.current = _follow_path(self.current, path)?;
+        Ok(())
+    }
+
+    /// Ascend 'current' n times.
+    ///
+    /// # Panics
+    /// This method will panic if 'current' has less than n ancestors. In this case 'current' is
+    /// left unchanged.
+    pub fn ascend_n(&mut self, n: usize) {
+        if let Err(err) = self.try_ascend_n(n) {
+            panic!("Tried to ascend {} times, but {}", n, err);
+        }
+    }
+
+    /// Same as [CursorMut::ascend_n], but returns an error instead of panicking.
+    pub fn try_ascend_n(&mut self, n: usize) -> Result<(), TreeError> {
+        self.current = _ascend_n(self.current, n)?;
+        Ok(())
+    }
+
+    /// Return a [NodeHandle] to 'current', see [crate::Tree::handle].
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push(1);
+    /// let mut cursor = tree.cursor_mut();
+    /// cursor.navigate_to(0);
+    /// let handle = cursor.handle();
+    /// tree.navigate_to_handle(&handle).unwrap();
+    /// assert_eq!(tree.peek(), &1);
+    /// ```
+    pub fn handle(&self) -> NodeHandle<T> {
+        NodeHandle::new(self.current)
+    }
+
     /// Return the number of childrens of current.
     ///
     /// # Examples
@@ -426,6 +945,90 @@ impl<'a, T> CursorMut<'a, T> {
         unsafe { (*self.current.as_ptr()).childs.len() }
     }
 
+    /// Return the number of nodes in the subtree rooted at 'current' (including 'current').
+    pub fn subtree_len(&self) -> usize {
+        _subtree_len(Some(self.current))
+    }
+
+    /// Return the distance between the root of the tree and 'current'.
+    pub fn depth(&self) -> usize {
+        _depth(Some(self.current))
+    }
+
+    /// Return the length of the longest path from 'current' to a leaf of its subtree.
+    pub fn height(&self) -> usize {
+        _height(Some(self.current))
+    }
+
+    /// Return the number of nodes without childs in the subtree rooted at 'current'.
+    pub fn leaf_count(&self) -> usize {
+        _leaf_count(Some(self.current))
+    }
+
+    /// Return a [TreeStats] report on the shape of the subtree rooted at 'current'.
+    pub fn stats(&self) -> TreeStats {
+        TreeStats::new(Some(self.current))
+    }
+
+    /// Return a [Render] drawing the subtree rooted at 'current'. See [Render] for the available
+    /// options. [Render::highlight_current] marks 'current' of the cursor, the first drawn node.
+    pub fn render(&self) -> Render<'_, T> {
+        Render::new(Some(self.current), Some(self.current))
+    }
+
+    /// See [Cursor::fold].
+    pub fn fold<R, F>(&self, f: F) -> R
+    where
+        F: FnMut(&T, Vec<R>) -> R,
+    {
+        _fold(self.current, f)
+    }
+
+    /// See [Cursor::fold_to_tree].
+    pub fn fold_to_tree<R, F>(&self, f: F) -> Tree<R>
+    where
+        F: FnMut(&T, Vec<&R>) -> R,
+    {
+        _fold_to_tree(self.current, f)
+    }
+
+    /// Iterate lazily over the subtree rooted at 'current' with the value accumulated along the
+    /// path from 'current', see [crate::Tree::scan_iter].
+    pub fn scan_iter<Acc, F>(&self, init: Acc, f: F) -> ScanIterator<'_, T, Acc, F>
+    where
+        Acc: Clone,
+        F: FnMut(&Acc, &T) -> Acc,
+    {
+        ScanIterator::new(Some(self.current), init, f)
+    }
+
+    /// Write the subtree rooted at 'current' as a Graphviz digraph to writer, see
+    /// [crate::Tree::to_dot].
+    pub fn to_dot<W: io::Write>(&self, writer: W) -> io::Result<()>
+    where
+        T: fmt::Display,
+    {
+        self.dot().write_to(writer)
+    }
+
+    /// Return a [Dot] exporter for the subtree rooted at 'current', labelling the nodes with the
+    /// [fmt::Display] of their elements. No node is marked as 'current'.
+    pub fn dot(&self) -> Dot<'_, T>
+    where
+        T: fmt::Display,
+    {
+        Dot::new(Some(self.current), None, |elem: &T| elem.to_string())
+    }
+
+    /// Return a [Dot] exporter for the subtree rooted at 'current', labelling the nodes with
+    /// label.
+    pub fn dot_with<'b, F>(&'b self, label: F) -> Dot<'b, T>
+    where
+        F: Fn(&T) -> String + 'b,
+    {
+        Dot::new(Some(self.current), None, label)
+    }
+
     /// Return an Iterator over the elements stored in 'current'.childs
     ///
     /// # Examples
@@ -480,7 +1083,7 @@ impl<'a, T> CursorMut<'a, T> {
     /// ```
     pub fn iter(&self) -> impl Iterator<Item = &'a T> {
         let mut container = Vec::new();
-        _iter_rec(self.current, &mut container);
+        _iter_dfs(self.current, &mut container);
         container.into_iter()
     }
 
@@ -495,7 +1098,7 @@ impl<'a, T> CursorMut<'a, T> {
     /// assert_eq!(tree.cursor_mut().iter_mut().collect::<Vec<&mut i32>>(), vec![&mut 2, &mut 4]);
     pub fn iter_mut(&mut self) -> impl Iterator<Item = &'a mut T> {
         let mut container = Vec::new();
-        _iter_rec_mut(self.current, &mut container);
+        _iter_dfs_mut(self.current, &mut container);
         container.into_iter()
     }
 
@@ -572,6 +1175,58 @@ impl<'a, T> CursorMut<'a, T> {
         }
     }
 
+    /// Iterate over the subtree rooted at 'current' in a lazy breadth-first way (level by level,
+    /// from left to right), returning references to the elements stored in the subtree.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// tree.navigate_to(0);
+    /// tree.push_iter(vec![3, 4]);
+    /// tree.ascend();
+    /// let cursor = tree.cursor_mut();
+    /// assert_eq!(cursor.iter_bfs().collect::<Vec<&i32>>(), vec![&0, &1, &2, &3, &4]);
+    /// ```
+    pub fn iter_bfs(&self) -> BfsIterator<'a, T> {
+        BfsIterator::new(Some(self.current))
+    }
+
+    /// Same as [CursorMut::iter_bfs], but returns mutable references instead.
+    pub fn iter_bfs_mut(&mut self) -> BfsIteratorMut<'a, T> {
+        BfsIteratorMut::new(Some(self.current))
+    }
+
+    /// Iterate over the levels of the subtree rooted at 'current', yielding a Vec with the
+    /// references of every node at the same depth ('current' being depth 0).
+    pub fn iter_levels(&self) -> LevelIterator<'a, T> {
+        LevelIterator::new(Some(self.current))
+    }
+
+    /// Iterate over the subtree rooted at 'current' in a lazy post-order way, meaning that the
+    /// childs of a node are always returned before it (and so 'current' is returned last).
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// tree.navigate_to(0);
+    /// tree.push_iter(vec![3, 4]);
+    /// tree.ascend();
+    /// let cursor = tree.cursor_mut();
+    /// assert_eq!(cursor.iter_postorder().collect::<Vec<&i32>>(), vec![&3, &4, &1, &2, &0]);
+    /// ```
+    pub fn iter_postorder(&self) -> PostOrderIterator<'a, T> {
+        PostOrderIterator::new(Some(self.current))
+    }
+
+    /// Same as [CursorMut::iter_postorder], but returns mutable references instead.
+    pub fn iter_postorder_mut(&mut self) -> PostOrderIteratorMut<'a, T> {
+        PostOrderIteratorMut::new(Some(self.current))
+    }
+
     /// Push el to 'current'.child as a new node in the tree.
     ///
     /// # Examples
@@ -592,6 +1247,7 @@ impl<'a, T> CursorMut<'a, T> {
                 .push(NonNull::new_unchecked(Box::into_raw(Box::new(Node {
                     elem: el,
                     childs: Vec::new(),
+                    token: OnceCell::new(),
                     father: Some(self.current),
                 }))))
         }
@@ -618,6 +1274,115 @@ impl<'a, T> CursorMut<'a, T> {
             self.push(el);
         }
     }
+
+    /// Remove the node 'current'.childs\[index\] from the tree and return its element, putting its
+    /// childs in its place. See [crate::Tree::remove_child_splice]. If 'current' of the tree sat
+    /// on the removed node, it moves to 'current' of the cursor.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push(1);
+    /// tree.navigate_to(0);
+    /// let mut cursor = tree.cursor_mut();
+    /// cursor.push_iter(vec![2, 3]);
+    /// cursor.ascend();
+    /// assert_eq!(cursor.remove_child_splice(0), 1);
+    /// assert_eq!(tree.iter().collect::<Vec<&i32>>(), vec![&0, &2, &3]);
+    /// assert_eq!(tree.peek(), &0);
+    /// ```
+    ///
+    /// # Panics
+    /// This method will panic if index >= self.childs_len
+    pub fn remove_child_splice(&mut self, index: usize) -> T {
+        if index >= self.childs_len() {
+            panic!(
+                "Tried to call remove_child_splice with index {} but current has only {} childs",
+                index,
+                self.childs_len()
+            );
+        }
+        let removed = unsafe { (&(*self.current.as_ptr()).childs)[index] };
+        unsafe {
+            if *self.tree_current.as_ptr() == Some(removed) {
+                *self.tree_current.as_ptr() = Some(self.current);
+            }
+        }
+        _remove_child_splice(self.current, index)
+    }
+
+    /// Same as [CursorMut::remove_child_splice], but returns an error instead of panicking.
+    pub fn try_remove_child_splice(&mut self, index: usize) -> Result<T, TreeError> {
+        let len = self.childs_len();
+        if index >= len {
+            return Err(TreeError::ChildOutOfRange { index, len });
+        }
+        Ok(self.remove_child_splice(index))
+    }
+
+    /// Remove from the subtree rooted at 'current' every subtree whose root does not satisfy f.
+    /// See [crate::Tree::retain].
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.prune_where(|el| !f(el));
+    }
+
+    /// Remove from the subtree rooted at 'current' every subtree whose root satisfies f.
+    /// 'current' itself is never tested nor removed. If 'current' of the tree was in a removed
+    /// subtree, it moves to the father of this subtree.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2, 3]);
+    /// let mut cursor = tree.cursor_mut();
+    /// cursor.navigate_to(0);
+    /// cursor.push_iter(vec![4, 5]);
+    /// cursor.prune_where(|el| *el > 4);
+    /// assert_eq!(tree.iter().collect::<Vec<&i32>>(), vec![&0, &1, &4, &2, &3]);
+    /// ```
+    pub fn prune_where<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.drain_filter(f);
+    }
+
+    /// Same as [CursorMut::prune_where], but the removed subtrees are returned as new trees
+    /// instead of being dropped. See [crate::Tree::drain_filter].
+    pub fn drain_filter<F>(&mut self, f: F) -> Vec<Tree<T>>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        // nodes below 'current' down to 'current' of the tree, if the latter is in the subtree
+        // rooted at the former. 'current' of the tree waits on 'current', which is never removed,
+        // so that it is never left on a freed node, even if f panics.
+        let mut path = Vec::new();
+        let mut link = unsafe { *self.tree_current.as_ptr() };
+        while let Some(node) = link.filter(|node| *node != self.current) {
+            path.push(node);
+            link = unsafe { (*node.as_ptr()).father };
+        }
+        if link.is_none() {
+            path.clear();
+        }
+        if !path.is_empty() {
+            unsafe { *self.tree_current.as_ptr() = Some(self.current) };
+        }
+
+        let removed = _drain_filter(self.current, f);
+        for node in path.into_iter().rev() {
+            if unsafe { (*node.as_ptr()).father.is_none() } {
+                break;
+            }
+            unsafe { *self.tree_current.as_ptr() = Some(node) };
+        }
+        removed
+    }
 }
 
 /// An unsafe version of [CursorMut]
@@ -745,7 +1510,39 @@ impl<'a, T> UnsafeCursor<'a, T> {
             );
         }
 
-        unsafe { &(*(*self.current.as_ptr()).childs[index].as_ptr()).elem }
+        unsafe { &(*(&(*self.current.as_ptr()).childs)[index].as_ptr()).elem }
+    }
+
+    /// Same as [UnsafeCursor::peek_child], but returns an error instead of panicking.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::{Tree, TreeError};
+    /// let mut tree = Tree::from_element(10);
+    /// tree.push(5);
+    /// let cursor = tree.unsafe_cursor();
+    /// assert_eq!(cursor.try_peek_child(0), Ok(&5));
+    /// assert_eq!(
+    ///     cursor.try_peek_child(1),
+    ///     Err(TreeError::ChildOutOfRange { index: 1, len: 1 })
+    /// );
+    /// ```
+    pub fn try_peek_child(&self, index: usize) -> Result<&'a T, TreeError> {
+        unsafe {
+            let childs = &(*self.current.as_ptr()).childs;
+            match childs.get(index) {
+                Some(child) => Ok(&(*child.as_ptr()).elem),
+                None => Err(TreeError::ChildOutOfRange {
+                    index,
+                    len: self.childs_len(),
+                }),
+            }
+        }
+    }
+
+    /// Same as [UnsafeCursor::peek_child], but returns None instead of panicking.
+    pub fn get_child(&self, index: usize) -> Option<&'a T> {
+        self.try_peek_child(index).ok()
     }
 
     /// Peek at 'current'.childs\[index\], returning a mutable reference to the element stored in
@@ -773,7 +1570,24 @@ impl<'a, T> UnsafeCursor<'a, T> {
             );
         }
 
-        unsafe { &mut (*(*self.current.as_ptr()).childs[index].as_ptr()).elem }
+        unsafe { &mut (*(&(*self.current.as_ptr()).childs)[index].as_ptr()).elem }
+    }
+
+    /// Same as [UnsafeCursor::peek_child_mut], but returns an error instead of panicking.
+    ///
+    /// # Safety
+    /// Same as [UnsafeCursor::peek_child_mut].
+    pub unsafe fn try_peek_child_mut(&self, index: usize) -> Result<&'a mut T, TreeError> {
+        unsafe {
+            let childs = &(*self.current.as_ptr()).childs;
+            match childs.get(index) {
+                Some(child) => Ok(&mut (*child.as_ptr()).elem),
+                None => Err(TreeError::ChildOutOfRange {
+                    index,
+                    len: self.childs_len(),
+                }),
+            }
+        }
     }
 
     /// Set 'current' to 'current'.childs\[index\], therefore navigating to this child
@@ -800,7 +1614,38 @@ impl<'a, T> UnsafeCursor<'a, T> {
         }
 
         unsafe {
-            self.current = (*self.current.as_ptr()).childs[index];
+            self.current = (&(*self.current.as_ptr()).childs)[index];
+        }
+    }
+
+    /// Same as [UnsafeCursor::navigate_to], but returns an error instead of panicking.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::{Tree, TreeError};
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push(1);
+    /// let mut cursor = tree.unsafe_cursor();
+    /// assert_eq!(
+    ///     cursor.try_navigate_to(2),
+    ///     Err(TreeError::ChildOutOfRange { index: 2, len: 1 })
+    /// );
+    /// assert_eq!(cursor.try_navigate_to(0), Ok(()));
+    /// assert_eq!(cursor.peek(), &1);
+    /// ```
+    pub fn try_navigate_to(&mut self, index: usize) -> Result<(), TreeError> {
+        unsafe {
+            let childs = &(*self.current.as_ptr()).childs;
+            match childs.get(index) {
+                Some(child) => {
+                    self.current = *child;
+                    Ok(())
+                }
+                None => Err(TreeError::ChildOutOfRange {
+                    index,
+                    len: self.childs_len(),
+                }),
+            }
         }
     }
 
@@ -829,6 +1674,25 @@ impl<'a, T> UnsafeCursor<'a, T> {
         }
     }
 
+    /// Same as [UnsafeCursor::ascend], but returns [TreeError::NoFather] instead of panicking.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::{Tree, TreeError};
+    /// let mut tree = Tree::from_element(0);
+    /// let mut cursor = tree.unsafe_cursor();
+    /// assert_eq!(cursor.try_ascend(), Err(TreeError::NoFather));
+    /// ```
+    pub fn try_ascend(&mut self) -> Result<(), TreeError> {
+        match unsafe { (*self.current.as_ptr()).father } {
+            Some(father) => {
+                self.current = father;
+                Ok(())
+            }
+            None => Err(TreeError::NoFather),
+        }
+    }
+
     /// Return true if 'current' has a father.
     ///
     /// # Examples
@@ -846,6 +1710,23 @@ impl<'a, T> UnsafeCursor<'a, T> {
         unsafe { (*self.current.as_ptr()).father.is_some() }
     }
 
+    /// Return a [NodeHandle] to 'current', see [crate::Tree::handle].
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push(1);
+    /// let mut cursor = tree.unsafe_cursor();
+    /// cursor.navigate_to(0);
+    /// let handle = cursor.handle();
+    /// tree.navigate_to_handle(&handle).unwrap();
+    /// assert_eq!(tree.peek(), &1);
+    /// ```
+    pub fn handle(&self) -> NodeHandle<T> {
+        NodeHandle::new(self.current)
+    }
+
     /// Return the number of childrens of current.
     ///
     /// # Examples
@@ -899,6 +1780,7 @@ impl<'a, T> UnsafeCursor<'a, T> {
                 .push(NonNull::new_unchecked(Box::into_raw(Box::new(Node {
                     elem: el,
                     childs: Vec::new(),
+                    token: OnceCell::new(),
                     father: Some(self.current),
                 }))))
         }
@@ -929,7 +1811,102 @@ impl<'a, T> UnsafeCursor<'a, T> {
 
 #[cfg(test)]
 mod test {
-    use super::super::Tree;
+    use super::super::{Tree, TreeError};
+
+    #[test]
+    fn try_navigation() {
+        let mut tree = Tree::from_element(0);
+        tree.push_iter(vec![1, 2]);
+        let mut cursor = tree.cursor();
+        assert_eq!(cursor.try_ascend(), Err(TreeError::NoFather));
+        assert_eq!(
+            cursor.try_navigate_to(2),
+            Err(TreeError::ChildOutOfRange { index: 2, len: 2 })
+        );
+        assert_eq!(cursor.get_child(1), Some(&2));
+        cursor.try_navigate_to(1).unwrap();
+        assert_eq!(cursor.peek(), &2);
+        assert_eq!(cursor.get_child(0), None);
+        cursor.try_ascend().unwrap();
+        assert_eq!(cursor.peek(), &0);
+
+        let mut cursor = tree.cursor_mut();
+        *cursor.get_child_mut(0).unwrap() = 10;
+        assert_eq!(
+            cursor.try_peek_child_mut(2).err(),
+            Some(TreeError::ChildOutOfRange { index: 2, len: 2 })
+        );
+        assert_eq!(tree.iter().collect::<Vec<&i32>>(), vec![&0, &10, &2]);
+    }
+
+    #[test]
+    fn prune_above_current() {
+        let mut tree = Tree::from_element(0);
+        tree.push_iter(vec![1, 2]);
+        tree.navigate_to(0);
+        tree.push(3);
+        tree.navigate_to(0);
+        tree.push(4);
+        tree.navigate_to(0);
+
+        // 'current' of the tree is not below the cursor
+        let mut cursor = tree.cursor_root_mut();
+        cursor.navigate_to(1);
+        cursor.push(5);
+        cursor.retain(|el| *el != 5);
+        assert_eq!(tree.peek(), &4);
+
+        // 'current' of the tree is below the cursor, but kept
+        tree.cursor_root_mut().prune_where(|el| *el == 2);
+        assert_eq!(tree.peek(), &4);
+        assert_eq!(tree.path(), vec![0, 0, 0]);
+
+        // 'current' of the tree is removed
+        let removed = tree.cursor_root_mut().drain_filter(|el| *el == 3);
+        assert_eq!(tree.peek(), &1);
+        assert_eq!(tree.path(), vec![0]);
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].iter().collect::<Vec<&i32>>(), vec![&3, &4]);
+
+        // 'current' of the tree is on a node still in the tree after f panics
+        tree.push(6);
+        tree.navigate_to(0);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let mut cursor = tree.cursor_root_mut();
+            cursor.prune_where(|el| if *el == 6 { panic!("f failed") } else { false })
+        }));
+        assert!(result.is_err());
+        assert_eq!(tree.peek(), &0);
+        assert_eq!(tree.iter().collect::<Vec<&i32>>(), vec![&0, &1, &6]);
+    }
+
+    #[test]
+    fn remove_child_splice_current() {
+        let mut tree = Tree::from_element(0);
+        tree.push_iter(vec![1, 2]);
+        tree.navigate_to(0);
+        tree.push(3);
+        tree.navigate_to(0);
+
+        let mut cursor = tree.cursor_root_mut();
+        assert_eq!(cursor.remove_child_splice(1), 2);
+        assert_eq!(
+            cursor.try_remove_child_splice(1),
+            Err(TreeError::ChildOutOfRange { index: 1, len: 1 })
+        );
+        assert_eq!(tree.peek(), &3);
+
+        let mut cursor = tree.cursor_root_mut();
+        cursor.navigate_to(0);
+        assert_eq!(cursor.try_remove_child_splice(0), Ok(3));
+        assert_eq!(tree.peek(), &1);
+        assert_eq!(tree.path(), vec![0]);
+
+        let mut cursor = tree.cursor_root_mut();
+        assert_eq!(cursor.remove_child_splice(0), 1);
+        assert_eq!(tree.peek(), &0);
+        assert_eq!(tree.iter().collect::<Vec<&i32>>(), vec![&0]);
+    }
 
     #[test]
     fn unsafe_cursor1() {
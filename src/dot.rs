@@ -0,0 +1,225 @@
+use crate::tree::{ChildLink, Link};
+use std::{collections::VecDeque, fmt, io, marker::PhantomData};
+
+/// Closure labelling the edge going to a child from its index and its element.
+type EdgeLabel<'a, T> = Box<dyn Fn(usize, &T) -> String + 'a>;
+
+/// Graphviz DOT exporter, returned by [crate::Tree::dot], [crate::Tree::dot_with] and the `dot`
+/// methods of the cursors.
+///
+/// Nodes are labelled with the node label closure (the [fmt::Display] of the elements by
+/// default), and edges are labelled with the optional edge label closure. Options are set with the
+/// builder methods, then the digraph is written with [Dot::write_to] (or with `{}`, as [Dot]
+/// implements [fmt::Display]). Nodes are written in breadth-first order, so limiting the number
+/// of nodes keeps the top of the tree.
+///
+/// # Examples
+/// ```
+/// # use libtree::Tree;
+/// let mut tree = Tree::from_element(0);
+/// tree.push_iter(vec![1, 2]);
+/// tree.navigate_to(1);
+/// let mut out = Vec::new();
+/// tree.dot()
+///     .edge_label(|index, _| format!("move {}", index))
+///     .write_to(&mut out)
+///     .unwrap();
+/// assert_eq!(
+///     String::from_utf8(out).unwrap(),
+///     "digraph {\n    \
+///          n0 [label=\"0\"];\n    \
+///          n1 [label=\"1\"];\n    \
+///          n0 -> n1 [label=\"move 0\"];\n    \
+///          n2 [label=\"2\", style=bold, color=red];\n    \
+///          n0 -> n2 [label=\"move 1\"];\n\
+///      }\n"
+/// );
+/// ```
+pub struct Dot<'a, T> {
+    start: Link<T>,
+    current: Link<T>,
+    node_label: Box<dyn Fn(&T) -> String + 'a>,
+    edge_label: Option<EdgeLabel<'a, T>>,
+    max_depth: Option<usize>,
+    max_nodes: Option<usize>,
+    mark_current: bool,
+    _boo: PhantomData<&'a T>,
+}
+
+impl<'a, T> Dot<'a, T> {
+    /// Export the subtree rooted at start, current being the node to mark.
+    pub(crate) fn new<F>(start: Link<T>, current: Link<T>, node_label: F) -> Self
+    where
+        F: Fn(&T) -> String + 'a,
+    {
+        Dot {
+            start,
+            current,
+            node_label: Box::new(node_label),
+            edge_label: None,
+            max_depth: None,
+            max_nodes: None,
+            mark_current: true,
+            _boo: PhantomData,
+        }
+    }
+
+    /// Label every node with f(elem).
+    pub fn node_label<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&T) -> String + 'a,
+    {
+        self.node_label = Box::new(f);
+        self
+    }
+
+    /// Label the edge going to the child number index of a node with f(index, child elem).
+    pub fn edge_label<F>(mut self, f: F) -> Self
+    where
+        F: Fn(usize, &T) -> String + 'a,
+    {
+        self.edge_label = Some(Box::new(f));
+        self
+    }
+
+    /// Only export the nodes up to depth (the first exported node being at depth 0).
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Stop after exporting n nodes.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// let dot = tree.dot().max_nodes(2).mark_current(false).to_string();
+    /// assert_eq!(
+    ///     dot,
+    ///     "digraph {\n    n0 [label=\"0\"];\n    n1 [label=\"1\"];\n    n0 -> n1;\n}\n"
+    /// );
+    /// ```
+    pub fn max_nodes(mut self, n: usize) -> Self {
+        self.max_nodes = Some(n);
+        self
+    }
+
+    /// If true (the default), draw the node where 'current' of the tree sits in bold red. Only
+    /// exporters made by the methods of [crate::Tree] know this node, the exporters of cursors
+    /// never mark anything.
+    pub fn mark_current(mut self, mark: bool) -> Self {
+        self.mark_current = mark;
+        self
+    }
+
+    /// Write the digraph to writer.
+    pub fn write_to<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        write!(writer, "{}", self)
+    }
+
+    /// Write the declaration of link, with id as its name.
+    fn write_node(&self, f: &mut fmt::Formatter<'_>, link: ChildLink<T>, id: usize) -> fmt::Result {
+        let label = (self.node_label)(unsafe { &(*link.as_ptr()).elem });
+        write!(f, "    n{} [label=\"{}\"", id, escape(&label))?;
+        if self.mark_current && Some(link) == self.current {
+            write!(f, ", style=bold, color=red")?;
+        }
+        writeln!(f, "];")
+    }
+}
+
+impl<'a, T> fmt::Display for Dot<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "digraph {{")?;
+        let max_nodes = self.max_nodes.unwrap_or(usize::MAX);
+        let mut queue = VecDeque::new();
+        let mut count = 0;
+        if let Some(start) = self.start {
+            if max_nodes > 0 {
+                self.write_node(f, start, 0)?;
+                queue.push_back((start, 0, 0));
+                count = 1;
+            }
+        }
+
+        'outer: while let Some((link, id, depth)) = queue.pop_front() {
+            if self.max_depth.is_some_and(|max_depth| depth >= max_depth) {
+                continue;
+            }
+            let childs = unsafe { &(*link.as_ptr()).childs };
+            for (index, child) in childs.iter().enumerate() {
+                if count == max_nodes {
+                    break 'outer;
+                }
+                self.write_node(f, *child, count)?;
+                write!(f, "    n{} -> n{}", id, count)?;
+                if let Some(edge_label) = &self.edge_label {
+                    let label = edge_label(index, unsafe { &(*child.as_ptr()).elem });
+                    write!(f, " [label=\"{}\"]", escape(&label))?;
+                }
+                writeln!(f, ";")?;
+                queue.push_back((*child, count, depth + 1));
+                count += 1;
+            }
+        }
+        writeln!(f, "}}")
+    }
+}
+
+/// Escape label to be written between double quotes in a DOT file.
+fn escape(label: &str) -> String {
+    label
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Tree;
+
+    #[test]
+    fn dot() {
+        let mut tree = Tree::from_element(String::from("root"));
+        tree.push_iter(vec![String::from("a \"quoted\""), String::from("b")]);
+        tree.navigate_to(0);
+        tree.push(String::from("c"));
+
+        let dot = tree.dot().mark_current(false).to_string();
+        assert_eq!(
+            dot,
+            "digraph {\n    \
+                 n0 [label=\"root\"];\n    \
+                 n1 [label=\"a \\\"quoted\\\"\"];\n    \
+                 n0 -> n1;\n    \
+                 n2 [label=\"b\"];\n    \
+                 n0 -> n2;\n    \
+                 n3 [label=\"c\"];\n    \
+                 n1 -> n3;\n\
+             }\n"
+        );
+
+        let dot = tree.dot().max_depth(1).to_string();
+        assert!(dot.contains("n1 [label=\"a \\\"quoted\\\"\", style=bold, color=red];"));
+        assert!(!dot.contains("n3"));
+
+        let dot = tree
+            .cursor()
+            .dot_with(|elem| elem.len().to_string())
+            .to_string();
+        assert_eq!(
+            dot,
+            "digraph {\n    \
+                 n0 [label=\"10\"];\n    \
+                 n1 [label=\"1\"];\n    \
+                 n0 -> n1;\n\
+             }\n"
+        );
+
+        let mut out = Vec::new();
+        Tree::<i32>::default().to_dot(&mut out).unwrap();
+        assert_eq!(out, b"digraph {\n}\n");
+    }
+}
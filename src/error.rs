@@ -0,0 +1,79 @@
+use std::{error::Error, fmt};
+
+/// Error returned by the fallible (`try_*`) methods of [crate::Tree] and of the cursors.
+///
+/// Every panicking method of the crate has a `try_*` counterpart returning this error instead of
+/// panicking, so that a bad index coming from user input does not crash the whole program.
+///
+/// # Examples
+/// ```
+/// # use libtree::{Tree, TreeError};
+/// let mut tree = Tree::from_element(0);
+/// tree.push(1);
+/// assert_eq!(
+///     tree.try_navigate_to(3),
+///     Err(TreeError::ChildOutOfRange { index: 3, len: 1 })
+/// );
+/// assert_eq!(tree.try_ascend(), Err(TreeError::NoFather));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TreeError {
+    /// The operation was called on an empty tree (i.e. 'root' is None).
+    EmptyTree,
+    /// The operation targeted 'current'.childs\[index\] but 'current' has only `len` childs.
+    ChildOutOfRange { index: usize, len: usize },
+    /// The operation tried to navigate up, but 'current' has no father.
+    NoFather,
+    /// The node designated by a [crate::NodeId] or a [crate::NodeHandle] has been removed from the
+    /// tree.
+    StaleNode,
+}
+
+impl fmt::Display for TreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TreeError::EmptyTree => write!(f, "the tree is empty"),
+            TreeError::ChildOutOfRange { index, len } => write!(
+                f,
+                "tried to access child {} but current has only {} childs",
+                index, len
+            ),
+            TreeError::NoFather => write!(f, "current has no father"),
+            TreeError::StaleNode => write!(f, "the node has been removed from the tree"),
+        }
+    }
+}
+
+impl Error for TreeError {}
+
+/// Error returned by the text format readers ([crate::Tree::from_newick] for example), locating
+/// the problem in the input.
+///
+/// # Examples
+/// ```
+/// # use libtree::Tree;
+/// let err = Tree::from_newick("(A,B;").unwrap_err();
+/// assert_eq!((err.line, err.column), (1, 5));
+/// assert_eq!(err.to_string(), "expected ',' or ')', found ';' at line 1, column 5");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParseError {
+    /// Line of the error, starting at 1.
+    pub line: usize,
+    /// Column of the error in characters, starting at 1.
+    pub column: usize,
+    /// What went wrong.
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+impl Error for ParseError {}
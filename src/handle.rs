@@ -0,0 +1,106 @@
+use crate::tree::ChildLink;
+use std::{
+    fmt,
+    rc::{Rc, Weak},
+};
+
+/// A stable handle to a node of a [crate::Tree]
+///
+/// Unlike cursors, a NodeHandle does not borrow the tree: it can be stored in your own structures
+/// while the tree keeps being mutated and navigated, and turned back later into a position with
+/// [crate::Tree::navigate_to_handle], [crate::Tree::cursor_at] or [crate::Tree::cursor_mut_at].
+///
+/// Handles are obtained from the tree or from any cursor (`handle()` methods).
+///
+/// ## Validity
+/// Every node lazily owns a token the first time a handle is taken on it, and the handle only
+/// keeps a weak reference to this token. When the node is freed (by [crate::Tree::into_vec], or
+/// by dropping the tree returned by [crate::Tree::split]), the token is freed with it, so the
+/// handle knows the node is gone without ever dereferencing it. If the node is still alive, the
+/// tree also checks that the node actually belongs to it (by ascending up to 'root'), so a handle
+/// to a node that was moved away by [crate::Tree::split] is rejected as well. In both cases,
+/// methods using the handle return [crate::TreeError::StaleNode].
+///
+/// # Examples
+/// ```
+/// # use libtree::{Tree, TreeError};
+/// let mut tree = Tree::from_element(0);
+/// tree.push_iter(vec![1, 2]);
+/// tree.navigate_to(1);
+/// let handle = tree.handle();
+/// tree.go_to_root();
+/// tree.navigate_to_handle(&handle).unwrap();
+/// assert_eq!(tree.peek(), &2);
+///
+/// tree.ascend();
+/// let split_tree = tree.split(1);
+/// assert_eq!(tree.navigate_to_handle(&handle), Err(TreeError::StaleNode));
+/// std::mem::drop(split_tree);
+/// assert!(!handle.is_alive());
+/// ```
+pub struct NodeHandle<T> {
+    pub(crate) node: ChildLink<T>,
+    pub(crate) token: Weak<()>,
+}
+
+impl<T> NodeHandle<T> {
+    /// Create a handle to the node pointed by link, creating the node token if needed.
+    pub(crate) fn new(link: ChildLink<T>) -> Self {
+        let token = unsafe { (*link.as_ptr()).token.get_or_init(|| Rc::new(())) };
+        NodeHandle {
+            node: link,
+            token: Rc::downgrade(token),
+        }
+    }
+
+    /// Return true if the node pointed by the handle has not been freed yet. Note that the node
+    /// can still be alive but belong to another tree (after a split for example).
+    pub fn is_alive(&self) -> bool {
+        self.token.strong_count() > 0
+    }
+
+    /// Return the node pointed by the handle if it is alive and if its root is root.
+    pub(crate) fn resolve(&self, root: ChildLink<T>) -> Option<ChildLink<T>> {
+        if !self.is_alive() {
+            return None;
+        }
+
+        let mut link = self.node;
+        unsafe {
+            while let Some(father) = (*link.as_ptr()).father {
+                link = father;
+            }
+        }
+        if link == root {
+            Some(self.node)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Clone for NodeHandle<T> {
+    fn clone(&self) -> Self {
+        NodeHandle {
+            node: self.node,
+            token: self.token.clone(),
+        }
+    }
+}
+
+impl<T> PartialEq for NodeHandle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.token.ptr_eq(&other.token)
+    }
+}
+
+impl<T> Eq for NodeHandle<T> {}
+
+impl<T> fmt::Debug for NodeHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NodeHandle")
+            .field("node", &self.node)
+            .field("alive", &self.is_alive())
+            .finish()
+    }
+}
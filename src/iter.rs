@@ -0,0 +1,448 @@
+use crate::tree::{ChildLink, Link, Node};
+use std::{collections::VecDeque, marker::PhantomData};
+
+/// Breadth-first (level-order) iterator over the references of a subtree.
+pub struct BfsIterator<'a, T> {
+    pub(crate) queue: VecDeque<ChildLink<T>>,
+    pub(crate) _boo: PhantomData<&'a T>,
+}
+
+impl<'a, T> BfsIterator<'a, T> {
+    /// Iterate over the subtree rooted at start, or over nothing if start is None.
+    pub(crate) fn new(start: Link<T>) -> Self {
+        BfsIterator {
+            queue: start.into_iter().collect(),
+            _boo: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Iterator for BfsIterator<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let link = self.queue.pop_front()?;
+        unsafe {
+            let node = &*link.as_ptr();
+            self.queue.extend(node.childs.iter());
+            Some(&node.elem)
+        }
+    }
+}
+
+/// Breadth-first (level-order) iterator over the mutable references of a subtree.
+pub struct BfsIteratorMut<'a, T> {
+    pub(crate) queue: VecDeque<ChildLink<T>>,
+    pub(crate) _boo: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> BfsIteratorMut<'a, T> {
+    /// Iterate over the subtree rooted at start, or over nothing if start is None.
+    pub(crate) fn new(start: Link<T>) -> Self {
+        BfsIteratorMut {
+            queue: start.into_iter().collect(),
+            _boo: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Iterator for BfsIteratorMut<'a, T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let link = self.queue.pop_front()?;
+        // Every node is visited only once, so the mutable references returned never alias.
+        unsafe {
+            let node = &mut *link.as_ptr();
+            self.queue.extend(node.childs.iter());
+            Some(&mut node.elem)
+        }
+    }
+}
+
+/// Iterator over the levels of a subtree, yielding the references of every node at the same
+/// depth together (in the same order as [BfsIterator]).
+pub struct LevelIterator<'a, T> {
+    pub(crate) level: Vec<ChildLink<T>>,
+    pub(crate) _boo: PhantomData<&'a T>,
+}
+
+impl<'a, T> LevelIterator<'a, T> {
+    /// Iterate over the subtree rooted at start, or over nothing if start is None.
+    pub(crate) fn new(start: Link<T>) -> Self {
+        LevelIterator {
+            level: start.into_iter().collect(),
+            _boo: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Iterator for LevelIterator<'a, T> {
+    type Item = Vec<&'a T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.level.is_empty() {
+            return None;
+        }
+
+        let mut next_level = Vec::new();
+        let mut elems = Vec::with_capacity(self.level.len());
+        for link in self.level.iter() {
+            unsafe {
+                let node = &*link.as_ptr();
+                next_level.extend(node.childs.iter());
+                elems.push(&node.elem);
+            }
+        }
+        self.level = next_level;
+        Some(elems)
+    }
+}
+
+/// Lazy post-order iterator (childs before their father) over the references of a subtree.
+///
+/// The iterator only stores the path from the root of the subtree to the node being explored,
+/// together with the index of the next child to explore for each node of the path.
+pub struct PostOrderIterator<'a, T> {
+    pub(crate) stack: Vec<(ChildLink<T>, usize)>,
+    pub(crate) _boo: PhantomData<&'a T>,
+}
+
+impl<'a, T> PostOrderIterator<'a, T> {
+    /// Iterate over the subtree rooted at start, or over nothing if start is None.
+    pub(crate) fn new(start: Link<T>) -> Self {
+        PostOrderIterator {
+            stack: start.into_iter().map(|link| (link, 0)).collect(),
+            _boo: PhantomData,
+        }
+    }
+}
+
+/// Advance a post-order exploration stack up to the next node whose childs have all been
+/// explored, and pop it.
+fn next_postorder<T>(stack: &mut Vec<(ChildLink<T>, usize)>) -> Option<ChildLink<T>> {
+    loop {
+        let (link, next_child) = stack.last_mut()?;
+        let childs = unsafe { &(*link.as_ptr()).childs };
+        if *next_child < childs.len() {
+            let child = childs[*next_child];
+            *next_child += 1;
+            stack.push((child, 0));
+        } else {
+            return stack.pop().map(|(link, _)| link);
+        }
+    }
+}
+
+impl<'a, T> Iterator for PostOrderIterator<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        next_postorder(&mut self.stack).map(|link| unsafe { &(*link.as_ptr()).elem })
+    }
+}
+
+/// Lazy post-order iterator (childs before their father) over the mutable references of a
+/// subtree.
+pub struct PostOrderIteratorMut<'a, T> {
+    pub(crate) stack: Vec<(ChildLink<T>, usize)>,
+    pub(crate) _boo: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> PostOrderIteratorMut<'a, T> {
+    /// Iterate over the subtree rooted at start, or over nothing if start is None.
+    pub(crate) fn new(start: Link<T>) -> Self {
+        PostOrderIteratorMut {
+            stack: start.into_iter().map(|link| (link, 0)).collect(),
+            _boo: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Iterator for PostOrderIteratorMut<'a, T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<Self::Item> {
+        // Every node is popped only once, so the mutable references returned never alias.
+        next_postorder(&mut self.stack).map(|link| unsafe { &mut (*link.as_ptr()).elem })
+    }
+}
+
+/// Lazy depth-first pre-order iterator over the references of a subtree, together with a value
+/// accumulated along the path from the first node, see [crate::Tree::scan_iter].
+pub struct ScanIterator<'a, T, Acc, F> {
+    /// Nodes left to visit, with the accumulated value of their father.
+    pub(crate) stack: Vec<(ChildLink<T>, Acc)>,
+    pub(crate) f: F,
+    pub(crate) _boo: PhantomData<&'a T>,
+}
+
+impl<'a, T, Acc, F> ScanIterator<'a, T, Acc, F> {
+    /// Iterate over the subtree rooted at start, or over nothing if start is None.
+    pub(crate) fn new(start: Link<T>, init: Acc, f: F) -> Self {
+        ScanIterator {
+            stack: start.map(|link| (link, init)).into_iter().collect(),
+            f,
+            _boo: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, Acc, F> Iterator for ScanIterator<'a, T, Acc, F>
+where
+    Acc: Clone,
+    F: FnMut(&Acc, &T) -> Acc,
+{
+    type Item = (&'a T, Acc);
+    fn next(&mut self) -> Option<Self::Item> {
+        let (link, father_acc) = self.stack.pop()?;
+        let node = unsafe { &*link.as_ptr() };
+        let acc = (self.f)(&father_acc, &node.elem);
+        self.stack
+            .extend(node.childs.iter().rev().map(|child| (*child, acc.clone())));
+        Some((&node.elem, acc))
+    }
+}
+
+/// Order in which an [IntoIter] yields the elements of a tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Traversal {
+    PreOrder,
+    PostOrder,
+    Bfs,
+}
+
+/// Consuming iterator over the elements of a [crate::Tree], created by
+/// [crate::Tree::into_iter], [crate::Tree::into_iter_preorder], [crate::Tree::into_iter_postorder]
+/// or [crate::Tree::into_iter_bfs].
+///
+/// Nodes are freed as soon as their element is yielded, so memory is given back while iterating.
+/// Dropping the iterator frees every remaining node.
+pub struct IntoIter<T> {
+    /// Nodes waiting to be yielded. The boolean is only used in post-order, and tells whether the
+    /// childs of the node have already been pushed (in which case the node is alone, its childs
+    /// being owned by the iterator). Otherwise, every node stored owns its whole subtree.
+    nodes: VecDeque<(ChildLink<T>, bool)>,
+    order: Traversal,
+}
+
+impl<T> IntoIter<T> {
+    fn new(root: Link<T>, order: Traversal) -> Self {
+        IntoIter {
+            nodes: root.into_iter().map(|link| (link, false)).collect(),
+            order,
+        }
+    }
+
+    pub(crate) fn preorder(root: Link<T>) -> Self {
+        IntoIter::new(root, Traversal::PreOrder)
+    }
+
+    pub(crate) fn postorder(root: Link<T>) -> Self {
+        IntoIter::new(root, Traversal::PostOrder)
+    }
+
+    pub(crate) fn bfs(root: Link<T>) -> Self {
+        IntoIter::new(root, Traversal::Bfs)
+    }
+}
+
+/// Free the node pointed by link (but not its childs), returning its element and its childs.
+fn take_node<T>(link: ChildLink<T>) -> (T, Vec<ChildLink<T>>) {
+    let node: Node<T> = unsafe { *Box::from_raw(link.as_ptr()) };
+    (node.elem, node.childs)
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.order {
+            Traversal::PreOrder => {
+                let (link, _) = self.nodes.pop_back()?;
+                let (elem, childs) = take_node(link);
+                self.nodes
+                    .extend(childs.into_iter().rev().map(|child| (child, false)));
+                Some(elem)
+            }
+            Traversal::Bfs => {
+                let (link, _) = self.nodes.pop_front()?;
+                let (elem, childs) = take_node(link);
+                self.nodes
+                    .extend(childs.into_iter().map(|child| (child, false)));
+                Some(elem)
+            }
+            Traversal::PostOrder => loop {
+                let (link, expanded) = self.nodes.pop_back()?;
+                if expanded {
+                    return Some(take_node(link).0);
+                }
+                self.nodes.push_back((link, true));
+                let childs = unsafe { &(*link.as_ptr()).childs };
+                self.nodes
+                    .extend(childs.iter().rev().map(|&child| (child, false)));
+            },
+        }
+    }
+}
+
+impl<T> Drop for IntoIter<T> {
+    fn drop(&mut self) {
+        for _ in self.by_ref() {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Tree;
+
+    fn sample() -> Tree<i32> {
+        let mut tree = Tree::from_element(0);
+        tree.push_iter(vec![1, 2, 3]);
+        tree.navigate_to(1);
+        tree.push_iter(vec![9, 8]);
+        tree.ascend();
+        tree.navigate_to(0);
+        tree.push_iter(vec![9, 10]);
+        tree.navigate_to(0);
+        tree.push(15);
+        tree.go_to_root();
+        tree
+    }
+
+    #[test]
+    fn scan() {
+        let mut tree = sample();
+        let depths: Vec<(i32, usize)> = tree
+            .scan_iter(0, |depth, _| depth + 1)
+            .map(|(elem, depth)| (*elem, depth))
+            .collect();
+        assert_eq!(
+            depths,
+            vec![
+                (0, 1),
+                (1, 2),
+                (9, 3),
+                (15, 4),
+                (10, 3),
+                (2, 2),
+                (9, 3),
+                (8, 3),
+                (3, 2)
+            ]
+        );
+
+        tree.navigate_to(1);
+        let sums: Vec<i32> = tree
+            .cursor()
+            .scan_iter(0, |sum, elem| sum + elem)
+            .map(|(_, sum)| sum)
+            .collect();
+        assert_eq!(sums, vec![2, 11, 10]);
+
+        let mut sums = tree.scan(100, |sum, elem| sum + elem);
+        assert_eq!(sums.peek(), &102);
+        sums.go_to_root();
+        assert_eq!(
+            sums.iter_bfs().collect::<Vec<_>>(),
+            vec![&100, &101, &102, &103, &110, &111, &111, &110, &125]
+        );
+    }
+
+    #[test]
+    fn bfs() {
+        let mut tree = sample();
+        assert_eq!(
+            tree.iter_bfs().collect::<Vec<&i32>>(),
+            vec![&0, &1, &2, &3, &9, &10, &9, &8, &15]
+        );
+        tree.navigate_to(0);
+        assert_eq!(
+            tree.iter_bfs().collect::<Vec<&i32>>(),
+            vec![&1, &9, &10, &15]
+        );
+        for el in tree.iter_bfs_mut() {
+            *el += 1;
+        }
+        tree.go_to_root();
+        assert_eq!(
+            tree.cursor().iter_bfs().collect::<Vec<&i32>>(),
+            vec![&0, &2, &2, &3, &10, &11, &9, &8, &16]
+        );
+        assert_eq!(Tree::<i32>::default().iter_bfs().count(), 0);
+    }
+
+    #[test]
+    fn postorder() {
+        let mut tree = sample();
+        assert_eq!(
+            tree.iter_postorder().collect::<Vec<&i32>>(),
+            vec![&15, &9, &10, &1, &9, &8, &2, &3, &0]
+        );
+        tree.navigate_to(1);
+        assert_eq!(
+            tree.iter_postorder().collect::<Vec<&i32>>(),
+            vec![&9, &8, &2]
+        );
+        let mut cursor = tree.cursor_root_mut();
+        cursor.navigate_to(0);
+        for el in cursor.iter_postorder_mut() {
+            *el *= 10;
+        }
+        assert_eq!(
+            tree.cursor_root().iter_postorder().collect::<Vec<&i32>>(),
+            vec![&150, &90, &100, &10, &9, &8, &2, &3, &0]
+        );
+        assert_eq!(Tree::<i32>::default().iter_postorder().count(), 0);
+    }
+
+    #[test]
+    fn into_iter() {
+        assert_eq!(
+            sample().into_iter().collect::<Vec<i32>>(),
+            vec![0, 1, 9, 15, 10, 2, 9, 8, 3]
+        );
+        assert_eq!(
+            sample().into_iter_postorder().collect::<Vec<i32>>(),
+            vec![15, 9, 10, 1, 9, 8, 2, 3, 0]
+        );
+        assert_eq!(
+            sample().into_iter_bfs().collect::<Vec<i32>>(),
+            vec![0, 1, 2, 3, 9, 10, 9, 8, 15]
+        );
+        assert_eq!(Tree::<i32>::default().into_iter().count(), 0);
+    }
+
+    #[test]
+    fn into_iter_partial_drop() {
+        use std::rc::Rc;
+
+        let token = Rc::new(());
+        let mut tree = Tree::from_element(Rc::clone(&token));
+        tree.push_iter(vec![Rc::clone(&token), Rc::clone(&token)]);
+        tree.navigate_to(0);
+        tree.push(Rc::clone(&token));
+        for order in 0..3 {
+            let mut iter = match order {
+                0 => tree.clone().into_iter_preorder(),
+                1 => tree.clone().into_iter_postorder(),
+                _ => tree.clone().into_iter_bfs(),
+            };
+            iter.next();
+            iter.next();
+            std::mem::drop(iter);
+            assert_eq!(Rc::strong_count(&token), 5);
+        }
+        std::mem::drop(tree);
+        assert_eq!(Rc::strong_count(&token), 1);
+    }
+
+    #[test]
+    fn levels() {
+        let mut tree = sample();
+        assert_eq!(
+            tree.iter_levels().collect::<Vec<Vec<&i32>>>(),
+            vec![vec![&0], vec![&1, &2, &3], vec![&9, &10, &9, &8], vec![&15]]
+        );
+        let mut cursor = tree.cursor_mut();
+        cursor.navigate_to(1);
+        assert_eq!(
+            cursor.iter_levels().collect::<Vec<Vec<&i32>>>(),
+            vec![vec![&2], vec![&9, &8]]
+        );
+    }
+}
@@ -9,7 +9,7 @@
 //! # Nomenclature
 //! Just a bit of nomemclature to be make my documentation slighty more readable.
 //! - a node is a set of pointers (one for the father and another collection for the childs) and
-//! also stores an element, often abbreviated into elem or el.
+//!   also stores an element, often abbreviated into elem or el.
 //! - a tree is composed of two pointers towards nodes :
 //!     - 'current', where you are in the tree.
 //!     - 'root', explicit.
@@ -44,9 +44,24 @@
 //! assert_eq!(tree.into_vec(), vec![1, 2, 3]);
 //! ```
 //!
+//! ## Errors
+//! Methods that can fail on a bad index or an empty tree panic. Each of them has a `try_*`
+//! counterpart returning a [TreeError] instead, for when the index comes from user input.
+//! ```
+//! # use libtree::{Tree, TreeError};
+//! let mut tree = Tree::from_element(1);
+//! tree.push(2);
+//! assert_eq!(
+//!     tree.try_navigate_to(1),
+//!     Err(TreeError::ChildOutOfRange { index: 1, len: 1 })
+//! );
+//! assert_eq!(tree.get_child(0), Some(&2));
+//! ```
+//!
 //! ## Joining and splitting
-//! `Tree`s can be joined and splitted. Splitting tree is for the moment the best way to drop
-//! unwantted part of the tree.
+//! `Tree`s can be joined and splitted. To drop unwanted parts of the tree, you can either split
+//! them one by one, or remove every subtree matching a predicate with `retain` or `prune_where`.
+//! To keep only the subtree rooted at 'current', use `reroot_at_current` (or `promote_child`).
 //! ```
 //! # use libtree::Tree;
 //! let mut tree1 = Tree::from_element(1);
@@ -93,8 +108,45 @@
 //! cursor.push(10);
 //! assert_eq!(cursor.iter_mut().collect::<Vec<&mut i32>>(), vec![&mut 2, &mut 10]);
 //! ```
+//!
+//! ## Serialization
+//! With the `serde` feature, [Tree] implements `Serialize` and `Deserialize` in a nested form,
+//! and `FlatTree` wraps a tree to (de)serialize it in a flat parent-index form, for deep trees.
+//! In both forms, the position of 'current' is saved with the tree.
+//!
+//! ## Game trees
+//! The [solve] module holds algorithms working on game trees, such as minimax, negamax,
+//! alpha-beta pruning (which can also grow the tree as it searches) and expectiminimax for games
+//! with chance nodes. The [mcts] module implements Monte Carlo tree search, with its search tree
+//! stored in a [Tree].
+mod arena;
 mod cursor;
+mod dot;
+mod error;
+mod handle;
+mod iter;
+pub mod mcts;
+mod newick;
+mod render;
+mod scanner;
+#[cfg(feature = "serde")]
+mod serialize;
+mod sexpr;
+pub mod solve;
+mod stats;
 mod tree;
 
+pub use arena::{ArenaCursor, ArenaCursorMut, ArenaIter, ArenaIterMut, ArenaTree, NodeId};
 pub use cursor::{Cursor, CursorMut, UnsafeCursor};
+pub use dot::Dot;
+pub use error::{ParseError, TreeError};
+pub use handle::NodeHandle;
+pub use iter::{
+    BfsIterator, BfsIteratorMut, IntoIter, LevelIterator, PostOrderIterator, PostOrderIteratorMut,
+    ScanIterator,
+};
+pub use render::Render;
+#[cfg(feature = "serde")]
+pub use serialize::FlatTree;
+pub use stats::TreeStats;
 pub use tree::Tree;
@@ -0,0 +1,381 @@
+//! Monte Carlo tree search.
+//!
+//! [Mcts] grows a search tree of [NodeStats] from the position where the search starts, one node
+//! per iteration, and gathers statistics on each move by playing random games (rollouts) from the
+//! new nodes. Each iteration:
+//! - selects a path from 'root', choosing among the childs of each node with a
+//!   [SelectionPolicy] ([Uct] by default), until reaching a node whose childs were never
+//!   generated.
+//! - expands this node by pushing all its childs, and goes to the first one (unless the node is an
+//!   end position).
+//! - plays a rollout from the node reached, giving a reward between 0 and 1.
+//! - backpropagates the reward up to 'root' through the fathers of the node.
+//!
+//! Rewards are always taken from the point of view of the player who made the move leading to a
+//! node (the player choosing it during selection), and the game is assumed to be a two players
+//! zero-sum game: a reward r for a node is a reward 1 - r for its father.
+//!
+//! # Examples
+//! ```
+//! use libtree::mcts::{BestBy, Mcts};
+//!
+//! // a game of nim with 5 sticks, where players take 1, 2 or 3 sticks, and the one taking the
+//! // last stick wins
+//! let expand = |sticks: &u32| (1..=*sticks.min(&3)).map(|taken| sticks - taken).collect();
+//! // a rollout where both players take sticks in turn, not so randomly
+//! let mut seed = 7;
+//! let rollout = |sticks: &u32| {
+//!     let mut sticks = *sticks;
+//!     let mut mover_wins = true;
+//!     while sticks > 0 {
+//!         seed = (seed * 17 + 5) % 61;
+//!         sticks -= (seed % 3 + 1).min(sticks);
+//!         mover_wins = !mover_wins;
+//!     }
+//!     if mover_wins { 1.0 } else { 0.0 }
+//! };
+//!
+//! let mut mcts = Mcts::new(5);
+//! mcts.search(2000, expand, rollout);
+//! // taking one stick leaves 4 sticks, a losing position for the opponent
+//! let best = mcts.best_child(BestBy::Visits).unwrap();
+//! assert_eq!(mcts.tree().peek_child(best).state, 4);
+//!
+//! // play this move, keeping what was learnt about the positions after it
+//! mcts.advance(best);
+//! assert_eq!(mcts.root().state, 4);
+//! assert!(mcts.root().visits > 0);
+//! ```
+use crate::{CursorMut, Tree};
+
+/// Node of the search tree of [Mcts].
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeStats<S> {
+    /// Position of the game.
+    pub state: S,
+    /// Number of rollouts played from this node or its descendants.
+    pub visits: u64,
+    /// Sum of the rewards of these rollouts, for the player who moved to this position.
+    pub reward: f64,
+    /// Whether the childs of the node were generated. A node can be expanded and have no childs
+    /// if it is an end position.
+    pub expanded: bool,
+}
+
+impl<S> NodeStats<S> {
+    /// Create the statistics of a node never visited.
+    pub fn new(state: S) -> Self {
+        NodeStats {
+            state,
+            visits: 0,
+            reward: 0.0,
+            expanded: false,
+        }
+    }
+
+    /// Return the mean reward of the node, or 0 if it was never visited.
+    pub fn mean_reward(&self) -> f64 {
+        if self.visits == 0 {
+            0.0
+        } else {
+            self.reward / self.visits as f64
+        }
+    }
+}
+
+/// Way of choosing the child to explore during the selection step of [Mcts].
+///
+/// This trait is implemented for closures taking the father and the child, so a policy can be
+/// given directly as a closure.
+///
+/// # Examples
+/// ```
+/// use libtree::mcts::{Mcts, NodeStats};
+///
+/// // always explore the least visited child
+/// let policy = |_: &NodeStats<u32>, child: &NodeStats<u32>| -(child.visits as f64);
+/// let mut mcts = Mcts::with_policy(3, policy);
+/// mcts.search(31, |n| if *n > 0 { vec![n - 1; 3] } else { vec![] }, |_| 1.0);
+/// let visits: Vec<u64> = mcts.tree().iter_childs().map(|child| child.visits).collect();
+/// assert_eq!(visits, vec![11, 10, 10]);
+/// ```
+pub trait SelectionPolicy<S> {
+    /// Return the score of child, a child of father. The child with the highest score is
+    /// selected, the first one if several childs have the same score.
+    fn score(&self, father: &NodeStats<S>, child: &NodeStats<S>) -> f64;
+}
+
+impl<S, F> SelectionPolicy<S> for F
+where
+    F: Fn(&NodeStats<S>, &NodeStats<S>) -> f64,
+{
+    fn score(&self, father: &NodeStats<S>, child: &NodeStats<S>) -> f64 {
+        self(father, child)
+    }
+}
+
+/// The UCT (Upper Confidence bounds applied to Trees) policy, scoring a child with
+/// `mean + exploration * sqrt(ln(father visits) / child visits)`. Childs never visited are
+/// always selected first.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Uct {
+    /// Weight of the exploration term, sqrt(2) by default.
+    pub exploration: f64,
+}
+
+impl Default for Uct {
+    fn default() -> Self {
+        Uct {
+            exploration: std::f64::consts::SQRT_2,
+        }
+    }
+}
+
+impl<S> SelectionPolicy<S> for Uct {
+    fn score(&self, father: &NodeStats<S>, child: &NodeStats<S>) -> f64 {
+        if child.visits == 0 {
+            return f64::INFINITY;
+        }
+        let explore = ((father.visits as f64).ln() / child.visits as f64).sqrt();
+        child.mean_reward() + self.exploration * explore
+    }
+}
+
+/// Criterion to choose the best move after a search, see [Mcts::best_child].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BestBy {
+    /// The most visited child, the most robust choice.
+    Visits,
+    /// The child with the highest mean reward.
+    Value,
+}
+
+/// Monte Carlo tree search, see the [module documentation](self).
+///
+/// The search tree is a [Tree] of [NodeStats], whose 'root' is the position to play from and
+/// 'current' stays at 'root'.
+#[derive(Debug)]
+pub struct Mcts<S, P = Uct> {
+    tree: Tree<NodeStats<S>>,
+    policy: P,
+}
+
+impl<S> Mcts<S> {
+    /// Create a search from state, with the [Uct] policy.
+    pub fn new(state: S) -> Self {
+        Mcts::with_policy(state, Uct::default())
+    }
+}
+
+impl<S, P> Mcts<S, P>
+where
+    P: SelectionPolicy<S>,
+{
+    /// Create a search from state, with the given selection policy.
+    pub fn with_policy(state: S, policy: P) -> Self {
+        Mcts {
+            tree: Tree::from_element(NodeStats::new(state)),
+            policy,
+        }
+    }
+
+    /// Return the search tree.
+    pub fn tree(&self) -> &Tree<NodeStats<S>> {
+        &self.tree
+    }
+
+    /// Return the search tree, consuming the search.
+    pub fn into_tree(self) -> Tree<NodeStats<S>> {
+        self.tree
+    }
+
+    /// Return the statistics of 'root', the position to play from.
+    pub fn root(&self) -> &NodeStats<S> {
+        self.tree.peek()
+    }
+
+    /// Run iterations iterations of the search. expand returns the positions reachable in one
+    /// move from a position (none for an end position), and rollout plays a game from a position
+    /// and returns the reward (between 0 and 1) of the player who moved to this position.
+    ///
+    /// The search can be resumed by calling this method again, the statistics being kept.
+    pub fn search<E, R>(&mut self, iterations: usize, mut expand: E, mut rollout: R)
+    where
+        E: FnMut(&S) -> Vec<S>,
+        R: FnMut(&S) -> f64,
+    {
+        for _ in 0..iterations {
+            let mut cursor = self.tree.cursor_mut();
+            let mut depth = 0;
+            while cursor.peek().expanded && cursor.childs_len() > 0 {
+                let index = _select(&self.policy, &cursor);
+                cursor.navigate_to(index);
+                depth += 1;
+            }
+
+            if !cursor.peek().expanded {
+                cursor.peek_mut().expanded = true;
+                let childs = expand(&cursor.peek().state);
+                cursor.push_iter(childs.into_iter().map(NodeStats::new));
+                if cursor.childs_len() > 0 {
+                    cursor.navigate_to(0);
+                    depth += 1;
+                }
+            }
+
+            let mut reward = rollout(&cursor.peek().state);
+            loop {
+                let stats = cursor.peek_mut();
+                stats.visits += 1;
+                stats.reward += reward;
+                if depth == 0 {
+                    break;
+                }
+                cursor.ascend();
+                depth -= 1;
+                reward = 1.0 - reward;
+            }
+        }
+    }
+
+    /// Return the index of the best move from 'root' according to by, or None if no child of
+    /// 'root' was visited. When several childs are equally good, the first one is chosen.
+    pub fn best_child(&self, by: BestBy) -> Option<usize> {
+        let mut best: Option<(usize, &NodeStats<S>)> = None;
+        for (index, child) in self.tree.iter_childs().enumerate() {
+            if child.visits == 0 {
+                continue;
+            }
+            let better = match (best, by) {
+                (None, _) => true,
+                (Some((_, best)), BestBy::Visits) => child.visits > best.visits,
+                (Some((_, best)), BestBy::Value) => child.mean_reward() > best.mean_reward(),
+            };
+            if better {
+                best = Some((index, child));
+            }
+        }
+        best.map(|(index, _)| index)
+    }
+
+    /// Play the move leading to the child number index of 'root': this child becomes the new
+    /// 'root', keeping the statistics of its subtree, and the rest of the tree is freed (see
+    /// [Tree::promote_child]).
+    ///
+    /// # Panics
+    /// This method will panic if index >= the number of childs of 'root', like
+    /// [Tree::promote_child].
+    pub fn advance(&mut self, index: usize) {
+        self.tree.promote_child(index);
+    }
+}
+
+/// Return the index of the child of the cursor with the highest score.
+fn _select<S, P>(policy: &P, cursor: &CursorMut<'_, NodeStats<S>>) -> usize
+where
+    P: SelectionPolicy<S>,
+{
+    let father = cursor.peek();
+    let mut best = (0, f64::NEG_INFINITY);
+    for (index, child) in cursor.iter_childs().enumerate() {
+        let score = policy.score(father, child);
+        if index == 0 || score > best.1 {
+            best = (index, score);
+        }
+    }
+    best.0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn nim(sticks: &u32) -> Vec<u32> {
+        (1..=*sticks.min(&3)).map(|taken| sticks - taken).collect()
+    }
+
+    /// Rollout playing nim with pseudo random moves.
+    fn nim_rollout() -> impl FnMut(&u32) -> f64 {
+        let mut seed: u64 = 42;
+        move |sticks| {
+            let mut sticks = *sticks;
+            let mut mover_wins = true;
+            while sticks > 0 {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                sticks -= (seed % 3 + 1).min(sticks as u64) as u32;
+                mover_wins = !mover_wins;
+            }
+            if mover_wins {
+                1.0
+            } else {
+                0.0
+            }
+        }
+    }
+
+    #[test]
+    fn search() {
+        let mut mcts = Mcts::new(10);
+        mcts.search(5000, nim, nim_rollout());
+        assert_eq!(mcts.root().visits, 5000);
+        let childs_visits: u64 = mcts.tree().iter_childs().map(|child| child.visits).sum();
+        assert_eq!(childs_visits, 5000);
+
+        // taking 2 sticks leaves 8 sticks, a losing position
+        assert_eq!(mcts.best_child(BestBy::Visits), Some(1));
+        assert_eq!(mcts.best_child(BestBy::Value), Some(1));
+
+        // the search can be resumed, and continued after a move
+        mcts.search(1000, nim, nim_rollout());
+        assert_eq!(mcts.root().visits, 6000);
+        let visits = mcts.tree().peek_child(1).visits;
+        mcts.advance(1);
+        assert_eq!(mcts.root().state, 8);
+        assert_eq!(mcts.root().visits, visits);
+        assert!(!mcts.tree().has_father());
+        mcts.search(100, nim, nim_rollout());
+        assert_eq!(mcts.root().visits, visits + 100);
+    }
+
+    #[test]
+    fn end_positions() {
+        let mut mcts = Mcts::new(0);
+        mcts.search(10, nim, nim_rollout());
+        assert_eq!(mcts.root().visits, 10);
+        assert!(mcts.root().expanded);
+        assert_eq!(mcts.best_child(BestBy::Visits), None);
+
+        // taking the last stick always wins
+        let mut mcts = Mcts::new(1);
+        mcts.search(10, nim, nim_rollout());
+        assert_eq!(mcts.tree().peek_child(0).mean_reward(), 1.0);
+        assert_eq!(mcts.root().mean_reward(), 0.0);
+    }
+
+    #[test]
+    fn deep() {
+        // a single move at each position, so each iteration goes one level deeper
+        let depth = 3000;
+        let mut mcts = Mcts::new(depth);
+        mcts.search(
+            depth as usize,
+            |n| if *n > 0 { vec![n - 1] } else { vec![] },
+            |_| 1.0,
+        );
+        assert_eq!(mcts.tree().height(), depth as usize);
+        let tree = mcts.into_tree();
+        assert_eq!(tree.peek().visits, depth as u64);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Tried to call promote_child with index 3 but current has only 3 childs"
+    )]
+    fn advance_out_of_range() {
+        let mut mcts = Mcts::new(5);
+        mcts.search(1, nim, nim_rollout());
+        mcts.advance(3);
+    }
+}
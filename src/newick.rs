@@ -0,0 +1,316 @@
+use crate::scanner::Scanner;
+use crate::{ParseError, Tree};
+
+/// Characters that end an unquoted label.
+const SPECIAL: &str = "()[]':;,";
+
+impl Tree<String> {
+    /// Read a tree written in the [Newick](https://en.wikipedia.org/wiki/Newick_format) format,
+    /// with 'current' at 'root'. The childs of a node are written between parentheses before its
+    /// label, so `(A,B)C;` is a tree with C as 'root' and A and B as childs.
+    ///
+    /// Labels can be quoted with `'` (a `''` inside a quoted label standing for a `'`), and
+    /// underscores in unquoted labels are read as spaces. Missing labels are read as empty
+    /// strings. Branch lengths (`:0.5`) are checked but discarded, see
+    /// [Tree::from_newick_with_lengths] to keep them. Comments between brackets are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let tree = Tree::from_newick("(A:0.1,'B''s':0.2,(C,D)E)F;").unwrap();
+    /// assert_eq!(tree.peek(), "F");
+    /// assert_eq!(
+    ///     tree.iter().collect::<Vec<_>>(),
+    ///     vec!["F", "A", "B's", "E", "C", "D"]
+    /// );
+    /// ```
+    pub fn from_newick(input: &str) -> Result<Self, ParseError> {
+        _parse_newick(input, |elem: &mut String, label, _| *elem = label)
+    }
+}
+
+impl Tree<(String, Option<f64>)> {
+    /// Same as [Tree::from_newick], but keep the branch length of each node (the length of the
+    /// edge coming from its father) next to its label.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_newick_with_lengths("(A:0.1,B)C:2;").unwrap();
+    /// assert_eq!(tree.peek(), &(String::from("C"), Some(2.0)));
+    /// tree.navigate_to(0);
+    /// assert_eq!(tree.peek(), &(String::from("A"), Some(0.1)));
+    /// tree.ascend();
+    /// tree.navigate_to(1);
+    /// assert_eq!(tree.peek(), &(String::from("B"), None));
+    /// ```
+    pub fn from_newick_with_lengths(input: &str) -> Result<Self, ParseError> {
+        _parse_newick(input, |elem: &mut (String, Option<f64>), label, length| {
+            *elem = (label, length)
+        })
+    }
+}
+
+impl<T> Tree<T> {
+    /// Write the whole tree in the Newick format, labelling each node with label. Labels are
+    /// quoted if they contain spaces or characters meaningful in Newick.
+    /// Note that this method will not panic if called on an empty tree, and returns `;`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// tree.navigate_to(1);
+    /// tree.push(3);
+    /// assert_eq!(tree.to_newick(|elem| elem.to_string()), "(1,(3)2)0;");
+    /// let tree = Tree::from_element("a b");
+    /// assert_eq!(tree.to_newick(|elem| elem.to_string()), "'a b';");
+    /// ```
+    pub fn to_newick<F>(&self, label: F) -> String
+    where
+        F: Fn(&T) -> String,
+    {
+        self.to_newick_with_lengths(|elem| (label(elem), None))
+    }
+
+    /// Same as [Tree::to_newick], but label returns the branch length of each node as well.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let newick = "(A:0.1,B)C:2;";
+    /// let tree = Tree::from_newick_with_lengths(newick).unwrap();
+    /// assert_eq!(tree.to_newick_with_lengths(|elem| elem.clone()), newick);
+    /// ```
+    pub fn to_newick_with_lengths<F>(&self, label: F) -> String
+    where
+        F: Fn(&T) -> (String, Option<f64>),
+    {
+        let mut out = String::new();
+        let mut cursor = match self.try_cursor_root() {
+            Ok(cursor) => cursor,
+            Err(_) => return String::from(";"),
+        };
+        // index of the next child to write for each node from 'root' to the cursor
+        let mut next = vec![0];
+        while let Some(index) = next.last_mut() {
+            if *index < cursor.childs_len() {
+                out.push(if *index == 0 { '(' } else { ',' });
+                cursor.navigate_to(*index);
+                *index += 1;
+                next.push(0);
+            } else {
+                if *index > 0 {
+                    out.push(')');
+                }
+                let (name, length) = label(cursor.peek());
+                _write_label(&mut out, &name);
+                if let Some(length) = length {
+                    out.push_str(&format!(":{}", length));
+                }
+                next.pop();
+                if !next.is_empty() {
+                    cursor.ascend();
+                }
+            }
+        }
+        out.push(';');
+        out
+    }
+}
+
+/// Read a Newick tree, storing the label and length of each node in its element with set.
+fn _parse_newick<T, F>(input: &str, mut set: F) -> Result<Tree<T>, ParseError>
+where
+    T: Default,
+    F: FnMut(&mut T, String, Option<f64>),
+{
+    let mut scanner = Scanner::new(input);
+    let mut tree = Tree::from_element(T::default());
+    let mut depth = 0;
+    loop {
+        // 'current' is a new node: open its childs, or read it as a leaf
+        _skip(&mut scanner)?;
+        if scanner.peek() == Some('(') {
+            scanner.bump();
+            depth += 1;
+            tree.push(T::default());
+            tree.navigate_to(tree.childs_len() - 1);
+            continue;
+        }
+        let (label, length) = _read_label(&mut scanner)?;
+        set(tree.peek_mut(), label, length);
+
+        // 'current' is complete: go to its next sibling, or close its father
+        loop {
+            _skip(&mut scanner)?;
+            match scanner.peek() {
+                Some(',') if depth > 0 => {
+                    scanner.bump();
+                    tree.ascend();
+                    tree.push(T::default());
+                    tree.navigate_to(tree.childs_len() - 1);
+                    break;
+                }
+                Some(')') if depth > 0 => {
+                    scanner.bump();
+                    depth -= 1;
+                    tree.ascend();
+                    let (label, length) = _read_label(&mut scanner)?;
+                    set(tree.peek_mut(), label, length);
+                }
+                Some(';') if depth == 0 => {
+                    scanner.bump();
+                    _skip(&mut scanner)?;
+                    if scanner.peek().is_some() {
+                        return Err(scanner.expected("end of input"));
+                    }
+                    return Ok(tree);
+                }
+                _ if depth > 0 => return Err(scanner.expected("',' or ')'")),
+                _ => return Err(scanner.expected("';'")),
+            }
+        }
+    }
+}
+
+/// Skip whitespaces and comments.
+fn _skip(scanner: &mut Scanner) -> Result<(), ParseError> {
+    loop {
+        scanner.bump_while(char::is_whitespace);
+        if scanner.peek() != Some('[') {
+            return Ok(());
+        }
+        let start = scanner.position();
+        scanner.bump_while(|c| c != ']');
+        if scanner.bump().is_none() {
+            return Err(Scanner::error_at(start, String::from("unclosed comment")));
+        }
+    }
+}
+
+/// Read the (possibly empty) label and the optional length of a node.
+fn _read_label(scanner: &mut Scanner) -> Result<(String, Option<f64>), ParseError> {
+    _skip(scanner)?;
+    let label = if scanner.peek() == Some('\'') {
+        let start = scanner.position();
+        scanner.bump();
+        let mut label = String::new();
+        loop {
+            label.push_str(&scanner.bump_while(|c| c != '\''));
+            if scanner.bump().is_none() {
+                return Err(Scanner::error_at(start, String::from("unclosed quote")));
+            }
+            if scanner.peek() != Some('\'') {
+                break label;
+            }
+            label.push('\'');
+            scanner.bump();
+        }
+    } else {
+        scanner
+            .bump_while(|c| !c.is_whitespace() && !SPECIAL.contains(c))
+            .replace('_', " ")
+    };
+
+    _skip(scanner)?;
+    if scanner.peek() != Some(':') {
+        return Ok((label, None));
+    }
+    scanner.bump();
+    _skip(scanner)?;
+    let start = scanner.position();
+    let length = scanner.bump_while(|c| c.is_ascii_alphanumeric() || "+-.".contains(c));
+    match length.parse() {
+        Ok(length) => Ok((label, Some(length))),
+        Err(_) if length.is_empty() => Err(scanner.expected("a branch length")),
+        Err(_) => Err(Scanner::error_at(
+            start,
+            format!("invalid branch length '{}'", length),
+        )),
+    }
+}
+
+/// Write label, quoted if needed.
+fn _write_label(out: &mut String, label: &str) {
+    if label
+        .chars()
+        .any(|c| c.is_whitespace() || c == '_' || SPECIAL.contains(c))
+    {
+        out.push('\'');
+        out.push_str(&label.replace('\'', "''"));
+        out.push('\'');
+    } else {
+        out.push_str(label);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ParseError, Tree};
+
+    #[test]
+    fn read() {
+        let tree = Tree::from_newick(
+            "[a comment] ((raccoon:19.2, bear:6.8)[another]:0.8,\n\
+             'sea lion':12,Sea_Otter:8)   ;",
+        )
+        .unwrap();
+        assert_eq!(
+            tree.iter().collect::<Vec<_>>(),
+            vec!["", "", "raccoon", "bear", "sea lion", "Sea Otter"]
+        );
+        assert_eq!(tree.childs_len(), 3);
+
+        let tree = Tree::from_newick_with_lengths("(,(,)B:1e-3);").unwrap();
+        let elems: Vec<_> = tree.iter().cloned().collect();
+        assert_eq!(elems[1], (String::new(), None));
+        assert_eq!(elems[2], (String::from("B"), Some(0.001)));
+        assert_eq!(tree.subtree_len(), 5);
+
+        assert_eq!(Tree::from_newick("A;").unwrap().peek(), "A");
+        assert_eq!(Tree::from_newick(";").unwrap().peek(), "");
+    }
+
+    #[test]
+    fn errors() {
+        let error = |input: &str| {
+            let ParseError {
+                line,
+                column,
+                message,
+            } = Tree::from_newick(input).unwrap_err();
+            (line, column, message)
+        };
+        assert_eq!(
+            error("(A,B)"),
+            (1, 6, String::from("expected ';', found end of input"))
+        );
+        assert_eq!(
+            error("(A,\n  B:x);"),
+            (2, 5, String::from("invalid branch length 'x'"))
+        );
+        assert_eq!(error("(A,'B);").2, "unclosed quote");
+        assert_eq!(error("(A[,B);").2, "unclosed comment");
+        assert_eq!(
+            error("A;B"),
+            (1, 3, String::from("expected end of input, found 'B'"))
+        );
+        assert_eq!(error("A)"), (1, 2, String::from("expected ';', found ')'")));
+        assert_eq!(
+            error("(A:;"),
+            (1, 4, String::from("expected a branch length, found ';'"))
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let newick = "((A:1,'B c':2.5)'it''s':0.5,(D)E,F_G:3);";
+        let tree = Tree::from_newick_with_lengths(newick).unwrap();
+        let written = tree.to_newick_with_lengths(|elem| elem.clone());
+        assert_eq!(written, "((A:1,'B c':2.5)'it''s':0.5,(D)E,'F G':3);");
+        assert_eq!(Tree::from_newick_with_lengths(&written).unwrap(), tree);
+        assert_eq!(Tree::<i32>::default().to_newick(|e| e.to_string()), ";");
+    }
+}
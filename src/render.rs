@@ -0,0 +1,227 @@
+use crate::tree::{ChildLink, Link};
+use std::{fmt, marker::PhantomData};
+
+/// Pretty printer drawing a subtree like the Unix `tree` command, returned by
+/// [crate::Tree::render], [crate::Tree::render_root] and the `render` methods of the cursors.
+///
+/// The drawing is done through [fmt::Display], so it can be printed with `{}` or turned into a
+/// [String] with `to_string`. Each node is written on its own line, its childs below it. Options
+/// are set with the builder methods before printing. The drawing is iterative, so very deep trees
+/// can be rendered.
+///
+/// # Examples
+/// ```
+/// # use libtree::Tree;
+/// let mut tree = Tree::from_element(0);
+/// tree.push_iter(vec![1, 2]);
+/// tree.navigate_to(0);
+/// tree.push(3);
+/// assert_eq!(
+///     tree.render_root().to_string(),
+///     "0\n├── 1\n│   └── 3\n└── 2"
+/// );
+/// assert_eq!(
+///     tree.render_root().ascii(true).highlight_current(true).to_string(),
+///     "0\n|-- 1 <\n|   `-- 3\n`-- 2"
+/// );
+/// ```
+pub struct Render<'a, T> {
+    start: Link<T>,
+    current: Link<T>,
+    max_depth: Option<usize>,
+    max_children: Option<usize>,
+    highlight_current: bool,
+    ascii: bool,
+    _boo: PhantomData<&'a T>,
+}
+
+impl<'a, T> Render<'a, T> {
+    /// Render the subtree rooted at start, current being the node to highlight.
+    pub(crate) fn new(start: Link<T>, current: Link<T>) -> Self {
+        Render {
+            start,
+            current,
+            max_depth: None,
+            max_children: None,
+            highlight_current: false,
+            ascii: false,
+            _boo: PhantomData,
+        }
+    }
+
+    /// Only draw the nodes up to depth (the first drawn node being at depth 0).
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push(1);
+    /// tree.navigate_to(0);
+    /// tree.push(2);
+    /// assert_eq!(tree.render_root().max_depth(1).to_string(), "0\n└── 1");
+    /// ```
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Only draw the first n childs of each node, the other ones being summarized in a
+    /// `... k more` line.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(1..=10);
+    /// assert_eq!(
+    ///     tree.render().max_children(2).to_string(),
+    ///     "0\n├── 1\n├── 2\n└── ... 8 more"
+    /// );
+    /// ```
+    pub fn max_children(mut self, n: usize) -> Self {
+        self.max_children = Some(n);
+        self
+    }
+
+    /// If true, append a `<` marker to the node where 'current' sits. With
+    /// [crate::Tree::render_root], this is 'current' of the tree. The other renders start at
+    /// 'current' (of the tree or of the cursor), so they mark the first drawn node.
+    pub fn highlight_current(mut self, highlight: bool) -> Self {
+        self.highlight_current = highlight;
+        self
+    }
+
+    /// If true, draw the branches with ASCII characters only instead of box drawing characters.
+    pub fn ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    /// Write the line of link, after the prefix and the connector.
+    fn write_node(&self, f: &mut fmt::Formatter<'_>, link: ChildLink<T>) -> fmt::Result
+    where
+        T: fmt::Display,
+    {
+        write!(f, "{}", unsafe { &(*link.as_ptr()).elem })?;
+        if self.highlight_current && Some(link) == self.current {
+            write!(f, " <")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T> fmt::Display for Render<'a, T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (branch, last, pipe, blank) = if self.ascii {
+            ("|-- ", "`-- ", "|   ", "    ")
+        } else {
+            ("├── ", "└── ", "│   ", "    ")
+        };
+        let start = match self.start {
+            Some(start) => start,
+            None => return Ok(()),
+        };
+
+        self.write_node(f, start)?;
+        // Stack of the nodes whose childs are being drawn, with the index of the next child to
+        // draw. The prefix holds the branches of every node of the stack but the last one.
+        let mut stack = Vec::new();
+        let mut prefix = String::new();
+        let mut prefix_lens = Vec::new();
+        if self.max_depth != Some(0) {
+            stack.push((start, 0));
+        }
+        while let Some((link, next)) = stack.last_mut() {
+            let childs = unsafe { &(*link.as_ptr()).childs };
+            let shown = childs.len().min(self.max_children.unwrap_or(usize::MAX));
+            if *next < shown {
+                let child = childs[*next];
+                *next += 1;
+                let is_last = *next == childs.len();
+                write!(f, "\n{}{}", prefix, if is_last { last } else { branch })?;
+                self.write_node(f, child)?;
+
+                let has_childs = unsafe { !(*child.as_ptr()).childs.is_empty() };
+                if has_childs && self.max_depth.is_none_or(|depth| stack.len() < depth) {
+                    prefix_lens.push(prefix.len());
+                    prefix.push_str(if is_last { blank } else { pipe });
+                    stack.push((child, 0));
+                }
+            } else if *next == shown && shown < childs.len() {
+                *next += 1;
+                write!(f, "\n{}{}... {} more", prefix, last, childs.len() - shown)?;
+            } else {
+                stack.pop();
+                if let Some(len) = prefix_lens.pop() {
+                    prefix.truncate(len);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Tree;
+
+    fn sample() -> Tree<i32> {
+        let mut tree = Tree::from_element(0);
+        tree.push_iter(vec![1, 2, 3]);
+        tree.navigate_to(0);
+        tree.push_iter(vec![4, 5]);
+        tree.navigate_to(1);
+        tree.push(6);
+        tree.go_to_root();
+        tree
+    }
+
+    #[test]
+    fn render() {
+        let mut tree = sample();
+        assert_eq!(
+            tree.render().to_string(),
+            "0\n├── 1\n│   ├── 4\n│   └── 5\n│       └── 6\n├── 2\n└── 3"
+        );
+        tree.navigate_path(&[0, 1]);
+        assert_eq!(
+            tree.render_root()
+                .ascii(true)
+                .highlight_current(true)
+                .max_children(1)
+                .to_string(),
+            "0\n|-- 1\n|   |-- 4\n|   `-- ... 1 more\n`-- ... 2 more"
+        );
+        assert_eq!(
+            tree.render().highlight_current(true).to_string(),
+            "5 <\n└── 6"
+        );
+        assert_eq!(
+            tree.cursor().render().highlight_current(true).to_string(),
+            "5 <\n└── 6"
+        );
+        tree.ascend();
+        assert_eq!(tree.render().to_string(), "1\n├── 4\n└── 5\n    └── 6");
+        assert_eq!(
+            tree.render_root().max_depth(1).max_children(0).to_string(),
+            "0\n└── ... 3 more"
+        );
+        assert_eq!(Tree::<i32>::default().render().to_string(), "");
+    }
+
+    #[test]
+    fn render_deep() {
+        let mut tree = Tree::from_element(0);
+        for i in 1..1_000 {
+            tree.push(i);
+            tree.navigate_to(0);
+        }
+        assert_eq!(tree.render().to_string(), "999");
+        let rendered = tree.render_root().to_string();
+        assert_eq!(rendered.lines().count(), 1_000);
+        assert!(rendered.ends_with("└── 999"));
+    }
+}
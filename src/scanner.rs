@@ -0,0 +1,78 @@
+use crate::ParseError;
+use std::{iter::Peekable, str::Chars};
+
+/// Character reader keeping track of the position in the input, shared by the text format
+/// readers.
+pub(crate) struct Scanner<'s> {
+    chars: Peekable<Chars<'s>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'s> Scanner<'s> {
+    pub(crate) fn new(input: &'s str) -> Self {
+        Scanner {
+            chars: input.chars().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    /// Return the next character without consuming it.
+    pub(crate) fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    /// Consume and return the next character.
+    pub(crate) fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    /// Consume the next characters while pred holds.
+    pub(crate) fn bump_while<F>(&mut self, mut pred: F) -> String
+    where
+        F: FnMut(char) -> bool,
+    {
+        let mut taken = String::new();
+        while let Some(c) = self.peek().filter(|c| pred(*c)) {
+            self.bump();
+            taken.push(c);
+        }
+        taken
+    }
+
+    /// Return the (line, column) of the next character.
+    pub(crate) fn position(&self) -> (usize, usize) {
+        (self.line, self.column)
+    }
+
+    /// Return an error located at the next character.
+    pub(crate) fn error(&self, message: String) -> ParseError {
+        Self::error_at(self.position(), message)
+    }
+
+    /// Return an error located at position.
+    pub(crate) fn error_at((line, column): (usize, usize), message: String) -> ParseError {
+        ParseError {
+            line,
+            column,
+            message,
+        }
+    }
+
+    /// Return an error located at the next character, saying that expected was expected there.
+    pub(crate) fn expected(&mut self, expected: &str) -> ParseError {
+        let found = match self.peek() {
+            Some(c) => format!("'{}'", c),
+            None => String::from("end of input"),
+        };
+        self.error(format!("expected {}, found {}", expected, found))
+    }
+}
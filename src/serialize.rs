@@ -0,0 +1,345 @@
+//! [serde] support, enabled by the `serde` feature.
+use crate::tree::{_attach, _path, ChildLink};
+use crate::{Tree, TreeError};
+use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+/// Serialize a [Tree] in the nested form `{ "root": node, "current": path }`, where each node is
+/// written as `{ "elem": ..., "childs": [...] }` (and root is null for an empty tree), and
+/// 'current' is written as its [Tree::path].
+///
+/// # Deep trees
+/// As each node is nested in its father, this form is not suited to deep trees:
+/// - writing a tree recurses once per level (the serde data model has no other way to write
+///   nested values), so serializing a tree with tens of thousands of levels can overflow the
+///   stack, whatever the serializer.
+/// - reading recurses once per level as well, for the same reason. Most deserializers bound the
+///   nesting (serde_json refuses to read more than 128 nested levels by default), but with a
+///   deserializer that has no such limit (or with the limit of serde_json disabled), reading a
+///   deeply nested input can overflow the stack. Do not read this form from untrusted input
+///   without such a limit.
+///
+/// Use [FlatTree] for deep trees, which is written and read without recursion.
+///
+/// # Examples
+/// ```
+/// # use libtree::Tree;
+/// let mut tree = Tree::from_element(0);
+/// tree.push_iter(vec![1, 2]);
+/// tree.navigate_to(1);
+/// let json = serde_json::to_string(&tree).unwrap();
+/// assert_eq!(
+///     json,
+///     r#"{"root":{"elem":0,"childs":[{"elem":1,"childs":[]},{"elem":2,"childs":[]}]},"current":[1]}"#
+/// );
+/// let tree: Tree<i32> = serde_json::from_str(&json).unwrap();
+/// assert_eq!(tree.peek(), &2);
+/// ```
+impl<T> Serialize for Tree<T>
+where
+    T: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (root, current) = self.links();
+        let mut state = serializer.serialize_struct("Tree", 2)?;
+        state.serialize_field("root", &root.map(NodeRef))?;
+        state.serialize_field("current", &current.map(_path).unwrap_or_default())?;
+        state.end()
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Tree<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = TreeRepr::deserialize(deserializer)?;
+        let mut tree = match repr.root {
+            Some(root) => _build(root),
+            None if repr.current.is_empty() => return Ok(Tree::default()),
+            None => return Err(D::Error::custom(TreeError::EmptyTree)),
+        };
+        tree.try_navigate_path(&repr.current)
+            .map_err(D::Error::custom)?;
+        Ok(tree)
+    }
+}
+
+/// Wrapper around a [Tree] serializing it in a flat parent-index form.
+///
+/// The tree is written as `{ "nodes": [...], "current": index }`, where nodes holds every node in
+/// pre-order as `{ "elem": ..., "father": index }` (the father of 'root' being null), and current
+/// is the index of 'current' in nodes (null for an empty tree). Unlike the nested form used by
+/// [Tree] itself, this form has no nesting, so any depth can be written and read back. When
+/// reading, nodes can come in any order as long as every father comes before its childs, and the
+/// childs of a node keep the order in which they appear.
+///
+/// # Examples
+/// ```
+/// # use libtree::{FlatTree, Tree};
+/// let mut tree = Tree::from_element(0);
+/// tree.push_iter(vec![1, 2]);
+/// tree.navigate_to(0);
+/// let json = serde_json::to_string(&FlatTree(tree)).unwrap();
+/// assert_eq!(
+///     json,
+///     r#"{"nodes":[{"elem":0,"father":null},{"elem":1,"father":0},{"elem":2,"father":0}],"current":1}"#
+/// );
+/// let FlatTree(tree) = serde_json::from_str::<FlatTree<i32>>(&json).unwrap();
+/// assert_eq!(tree.peek(), &1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FlatTree<T>(pub Tree<T>);
+
+impl<T> From<Tree<T>> for FlatTree<T> {
+    fn from(tree: Tree<T>) -> Self {
+        FlatTree(tree)
+    }
+}
+
+impl<T> From<FlatTree<T>> for Tree<T> {
+    fn from(flat: FlatTree<T>) -> Self {
+        flat.0
+    }
+}
+
+impl<T> Serialize for FlatTree<T>
+where
+    T: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (root, current) = self.0.links();
+        // (node, index of its father) in pre-order
+        let mut nodes = Vec::new();
+        let mut stack: Vec<(ChildLink<T>, Option<usize>)> =
+            root.map(|root| (root, None)).into_iter().collect();
+        while let Some((link, father)) = stack.pop() {
+            let index = nodes.len();
+            nodes.push((link, father));
+            let childs = unsafe { &(*link.as_ptr()).childs };
+            stack.extend(childs.iter().rev().map(|child| (*child, Some(index))));
+        }
+        let current = nodes.iter().position(|(link, _)| Some(*link) == current);
+
+        let mut state = serializer.serialize_struct("FlatTree", 2)?;
+        state.serialize_field(
+            "nodes",
+            &Seq(nodes.iter().map(|(link, father)| FlatNodeRef {
+                elem: unsafe { &(*link.as_ptr()).elem },
+                father: *father,
+            })),
+        )?;
+        state.serialize_field("current", &current)?;
+        state.end()
+    }
+}
+
+impl<'de, T> Deserialize<'de> for FlatTree<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = FlatRepr::<T>::deserialize(deserializer)?;
+        if repr.nodes.is_empty() {
+            return match repr.current {
+                None => Ok(FlatTree(Tree::default())),
+                Some(_) => Err(D::Error::custom(TreeError::EmptyTree)),
+            };
+        }
+        let current = match repr.current {
+            Some(current) if current < repr.nodes.len() => current,
+            Some(current) => {
+                return Err(D::Error::custom(format!(
+                    "current is {} but there are only {} nodes",
+                    current,
+                    repr.nodes.len()
+                )))
+            }
+            None => {
+                return Err(D::Error::custom(
+                    "current is null but the tree is not empty",
+                ))
+            }
+        };
+
+        for (index, node) in repr.nodes.iter().enumerate() {
+            match (index, node.father) {
+                (0, None) => (),
+                (0, Some(_)) => return Err(D::Error::custom("the first node has a father")),
+                (_, Some(father)) if father < index => (),
+                (_, Some(father)) => {
+                    return Err(D::Error::custom(format!(
+                        "node {} has father {}, which does not come before it",
+                        index, father
+                    )))
+                }
+                (_, None) => return Err(D::Error::custom(format!("node {} has no father", index))),
+            }
+        }
+
+        let mut links: Vec<ChildLink<T>> = Vec::with_capacity(repr.nodes.len());
+        for node in repr.nodes {
+            let father = node.father.map(|father| links[father]);
+            links.push(_attach(node.elem, father));
+        }
+        Ok(FlatTree(Tree::from_links(
+            Some(links[0]),
+            Some(links[current]),
+        )))
+    }
+}
+
+/// Reference to a node, serialized as `{ "elem": ..., "childs": [...] }`. Serializing it recurses
+/// in its childs, see the limits of the nested form on [Tree].
+struct NodeRef<T>(ChildLink<T>);
+
+impl<T> Serialize for NodeRef<T>
+where
+    T: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let node = unsafe { &*self.0.as_ptr() };
+        let mut state = serializer.serialize_struct("Node", 2)?;
+        state.serialize_field("elem", &node.elem)?;
+        state.serialize_field("childs", &Seq(node.childs.iter().map(|c| NodeRef(*c))))?;
+        state.end()
+    }
+}
+
+/// Serialize the items of an iterator as a sequence.
+struct Seq<I>(I);
+
+impl<I> Serialize for Seq<I>
+where
+    I: Iterator + Clone,
+    I::Item: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.clone())
+    }
+}
+
+#[derive(serde::Serialize)]
+struct FlatNodeRef<'a, T> {
+    elem: &'a T,
+    father: Option<usize>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename = "Tree")]
+struct TreeRepr<T> {
+    root: Option<NodeRepr<T>>,
+    current: Vec<usize>,
+}
+
+/// A node of the nested form. Deserializing it recurses once per level of the tree.
+#[derive(serde::Deserialize)]
+#[serde(rename = "Node")]
+struct NodeRepr<T> {
+    elem: T,
+    childs: Vec<NodeRepr<T>>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename = "FlatTree")]
+struct FlatRepr<T> {
+    nodes: Vec<FlatNode<T>>,
+    current: Option<usize>,
+}
+
+#[derive(serde::Deserialize)]
+struct FlatNode<T> {
+    elem: T,
+    father: Option<usize>,
+}
+
+/// Turn a nested representation into a tree, without recursion.
+fn _build<T>(root: NodeRepr<T>) -> Tree<T> {
+    let mut stack = vec![(root, None)];
+    let mut tree_root = None;
+    while let Some((repr, father)) = stack.pop() {
+        let link = _attach(repr.elem, father);
+        tree_root.get_or_insert(link);
+        stack.extend(
+            repr.childs
+                .into_iter()
+                .rev()
+                .map(|child| (child, Some(link))),
+        );
+    }
+    Tree::from_links(tree_root, tree_root)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{FlatTree, Tree};
+
+    fn sample() -> Tree<String> {
+        let mut tree = Tree::from_element(String::from("a"));
+        tree.push_iter(vec![String::from("b"), String::from("c")]);
+        tree.navigate_to(1);
+        tree.push(String::from("d"));
+        tree.navigate_to(0);
+        tree
+    }
+
+    #[test]
+    fn nested() {
+        let tree = sample();
+        let json = serde_json::to_string(&tree).unwrap();
+        let read: Tree<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(read, tree);
+        assert_eq!(read.path(), vec![1, 0]);
+
+        let empty: Tree<String> =
+            serde_json::from_str(&serde_json::to_string(&Tree::<String>::default()).unwrap())
+                .unwrap();
+        assert!(empty.is_empty());
+
+        let bad = r#"{"root":{"elem":"a","childs":[]},"current":[0]}"#;
+        let err = serde_json::from_str::<Tree<String>>(bad).unwrap_err();
+        assert!(err.to_string().contains("tried to access child 0"));
+    }
+
+    #[test]
+    fn flat() {
+        let tree = sample();
+        let json = serde_json::to_string(&FlatTree(tree.clone())).unwrap();
+        let FlatTree(read) = serde_json::from_str(&json).unwrap();
+        assert_eq!(read, tree);
+        assert_eq!(read.path(), vec![1, 0]);
+
+        // fathers only need to come before their childs
+        let json = r#"{"nodes":[
+            {"elem":"a","father":null},
+            {"elem":"c","father":0},
+            {"elem":"d","father":1},
+            {"elem":"e","father":0}
+        ],"current":3}"#;
+        let FlatTree(read) = serde_json::from_str::<FlatTree<String>>(json).unwrap();
+        assert_eq!(read.path(), vec![1]);
+        assert_eq!(read.subtree_len(), 1);
+
+        for bad in [
+            r#"{"nodes":[{"elem":"a","father":null},{"elem":"b","father":1}],"current":0}"#,
+            r#"{"nodes":[{"elem":"a","father":null},{"elem":"b","father":null}],"current":0}"#,
+            r#"{"nodes":[{"elem":"a","father":null}],"current":1}"#,
+            r#"{"nodes":[],"current":0}"#,
+        ] {
+            assert!(serde_json::from_str::<FlatTree<String>>(bad).is_err());
+        }
+    }
+
+    #[test]
+    fn flat_deep() {
+        let mut tree = Tree::from_element(0);
+        for i in 1..100_000 {
+            tree.push(i);
+            tree.navigate_to(0);
+        }
+        let json = serde_json::to_string(&FlatTree(tree)).unwrap();
+        let FlatTree(read) = serde_json::from_str::<FlatTree<i32>>(&json).unwrap();
+        assert_eq!(read.depth(), 99_999);
+        assert_eq!(read.peek(), &99_999);
+    }
+}
@@ -0,0 +1,237 @@
+use crate::scanner::Scanner;
+use crate::{Cursor, ParseError, Tree};
+use std::{fmt, marker::PhantomData, str::FromStr};
+
+impl<T> Tree<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    /// Read a tree written as an S-expression, with 'current' at 'root'. A node with childs is
+    /// written as a list starting with its element followed by its childs, and a leaf is written
+    /// as its element alone, so `(1 (2 4 5) 3)` is a tree with 1 as 'root', 2 and 3 as childs,
+    /// and 4 and 5 as childs of 2. Elements are separated by whitespaces and parsed with
+    /// [FromStr]. The same tree can also be read with `parse`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let tree = Tree::<i32>::from_sexpr("(1 (2 4 5) 3)").unwrap();
+    /// assert_eq!(tree.iter().collect::<Vec<_>>(), vec![&1, &2, &4, &5, &3]);
+    ///
+    /// let err = Tree::<i32>::from_sexpr("(1 (2 x))").unwrap_err();
+    /// assert_eq!((err.line, err.column), (1, 7));
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "invalid element 'x': invalid digit found in string at line 1, column 7"
+    /// );
+    /// ```
+    pub fn from_sexpr(input: &str) -> Result<Self, ParseError> {
+        let mut scanner = Scanner::new(input);
+        scanner.bump_while(char::is_whitespace);
+        let mut tree;
+        let mut depth = 0;
+        if scanner.peek() == Some('(') {
+            scanner.bump();
+            tree = Tree::from_element(_read_elem(&mut scanner)?);
+            depth = 1;
+        } else {
+            tree = Tree::from_element(_read_elem(&mut scanner)?);
+        }
+
+        while depth > 0 {
+            scanner.bump_while(char::is_whitespace);
+            match scanner.peek() {
+                Some('(') => {
+                    scanner.bump();
+                    tree.push(_read_elem(&mut scanner)?);
+                    tree.navigate_to(tree.childs_len() - 1);
+                    depth += 1;
+                }
+                Some(')') => {
+                    scanner.bump();
+                    depth -= 1;
+                    if depth > 0 {
+                        tree.ascend();
+                    }
+                }
+                Some(_) => tree.push(_read_elem(&mut scanner)?),
+                None => return Err(scanner.expected("')'")),
+            }
+        }
+
+        scanner.bump_while(char::is_whitespace);
+        if scanner.peek().is_some() {
+            return Err(scanner.expected("end of input"));
+        }
+        Ok(tree)
+    }
+}
+
+impl<T> FromStr for Tree<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    type Err = ParseError;
+
+    /// See [Tree::from_sexpr].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Tree::from_sexpr(s)
+    }
+}
+
+impl<T> Tree<T>
+where
+    T: fmt::Display,
+{
+    /// Write the whole tree as an S-expression, see [Tree::from_sexpr] for the syntax. The tree
+    /// can only be read back if no element is written with whitespaces or parentheses.
+    /// Note that this method will not panic if called on an empty tree, and returns an empty
+    /// string.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(1);
+    /// tree.push_iter(vec![2, 3]);
+    /// tree.navigate_to(0);
+    /// tree.push_iter(vec![4, 5]);
+    /// assert_eq!(tree.to_sexpr(), "(1 (2 4 5) 3)");
+    /// ```
+    pub fn to_sexpr(&self) -> String {
+        match self.try_cursor_root() {
+            Ok(cursor) => cursor.to_sexpr(),
+            Err(_) => String::new(),
+        }
+    }
+}
+
+impl<'a, T> Cursor<'a, T>
+where
+    T: fmt::Display,
+{
+    /// Write the subtree rooted at 'current' as an S-expression, see [Tree::to_sexpr].
+    pub fn to_sexpr(&self) -> String {
+        let mut out = String::new();
+        let mut cursor = Cursor {
+            current: self.current,
+            _boo: PhantomData,
+        };
+        // index of the next child to write for each node from self to cursor
+        let mut next = vec![0];
+        while let Some(index) = next.last_mut() {
+            if *index == 0 {
+                if cursor.childs_len() > 0 {
+                    out.push('(');
+                }
+                out.push_str(&cursor.peek().to_string());
+            }
+            if *index < cursor.childs_len() {
+                out.push(' ');
+                cursor.navigate_to(*index);
+                *index += 1;
+                next.push(0);
+            } else {
+                if *index > 0 {
+                    out.push(')');
+                }
+                next.pop();
+                if !next.is_empty() {
+                    cursor.ascend();
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Read an element, after optional whitespaces.
+fn _read_elem<T>(scanner: &mut Scanner) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    scanner.bump_while(char::is_whitespace);
+    let start = scanner.position();
+    let atom = scanner.bump_while(|c| !c.is_whitespace() && c != '(' && c != ')');
+    if atom.is_empty() {
+        return Err(scanner.expected("an element"));
+    }
+    atom.parse()
+        .map_err(|err| Scanner::error_at(start, format!("invalid element '{}': {}", atom, err)))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Tree;
+
+    #[test]
+    fn round_trip() {
+        for input in ["(1 (2 4 5) 3)", "7", "(1 2 (3 (4 (5 6))) 7)", "(0 (1 2))"] {
+            let tree: Tree<i32> = input.parse().unwrap();
+            assert_eq!(tree.to_sexpr(), input);
+            let atoms: Vec<i32> = input
+                .split(|c: char| c == '(' || c == ')' || c.is_whitespace())
+                .filter(|atom| !atom.is_empty())
+                .map(|atom| atom.parse().unwrap())
+                .collect();
+            assert_eq!(tree.iter().copied().collect::<Vec<_>>(), atoms);
+        }
+
+        let mut tree = Tree::from_element(String::from("a"));
+        tree.push_iter(vec![String::from("b"), String::from("c")]);
+        tree.navigate_to(1);
+        tree.push(String::from("d"));
+        let written = tree.to_sexpr();
+        assert_eq!(written, "(a b (c d))");
+        assert_eq!(written.parse::<Tree<String>>().unwrap(), tree);
+        assert_eq!(tree.cursor().to_sexpr(), "(c d)");
+        assert_eq!(Tree::<i32>::default().to_sexpr(), "");
+    }
+
+    #[test]
+    fn whitespaces() {
+        let tree: Tree<i32> = " \n( 1\n\t(2  4 5 )3 )  ".parse().unwrap();
+        assert_eq!(tree.to_sexpr(), "(1 (2 4 5) 3)");
+        assert_eq!(tree.peek(), &1);
+    }
+
+    #[test]
+    fn errors() {
+        let error = |input: &str| {
+            let err = input.parse::<Tree<i32>>().unwrap_err();
+            (err.line, err.column, err.message)
+        };
+        assert_eq!(
+            error("(1 (2 3)"),
+            (1, 9, String::from("expected ')', found end of input"))
+        );
+        assert_eq!(
+            error("(1 2))"),
+            (1, 6, String::from("expected end of input, found ')'"))
+        );
+        assert_eq!(
+            error("(1\n  ())"),
+            (2, 4, String::from("expected an element, found ')'"))
+        );
+        assert_eq!(
+            error(""),
+            (
+                1,
+                1,
+                String::from("expected an element, found end of input")
+            )
+        );
+        assert_eq!(error("(1 2 y)").1, 6);
+    }
+
+    #[test]
+    fn deep() {
+        let depth = 100_000;
+        let input = format!("{}0{}", "(0 ".repeat(depth), ")".repeat(depth));
+        let tree: Tree<i32> = input.parse().unwrap();
+        assert_eq!(tree.height(), depth);
+        assert_eq!(tree.to_sexpr(), input);
+    }
+}
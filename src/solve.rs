@@ -0,0 +1,1060 @@
+//! Game tree solvers.
+//!
+//! Solvers work on a [Tree] whose elements are game positions: the childs of a node are the
+//! positions reachable in one move, and the leaves are the end positions (or the positions where
+//! the exploration was stopped). They take two closures, one evaluating a leaf from the point of
+//! view of [Player::Max], and the other telling which player is to move at a node. Only the
+//! subtree rooted at 'current' is solved.
+//!
+//! # Examples
+//! ```
+//! # use libtree::Tree;
+//! use libtree::solve::{minimax, Player};
+//!
+//! // internal nodes tell who is to move, leaves hold their score
+//! let tree: Tree<String> = "(max (min 3 12 8) (min 2 4 6) (min 14 5 2))".parse().unwrap();
+//! let solution = minimax(
+//!     &tree,
+//!     |leaf| leaf.parse::<i32>().unwrap(),
+//!     |node| if node == "min" { Player::Min } else { Player::Max },
+//! );
+//! assert_eq!(solution.value, 3);
+//! assert_eq!(solution.principal_variation, vec![0, 0]);
+//! ```
+use crate::{Cursor, CursorMut, Tree};
+use std::{cmp::Ordering, ops::Neg};
+
+/// The two players of a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Player {
+    /// The player trying to maximize the evaluation.
+    Max,
+    /// The player trying to minimize the evaluation.
+    Min,
+}
+
+impl Player {
+    /// Return the other player.
+    pub fn opponent(self) -> Self {
+        match self {
+            Player::Max => Player::Min,
+            Player::Min => Player::Max,
+        }
+    }
+}
+
+/// Result of a solver, for the node where 'current' sits.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution<V> {
+    /// Value of the node.
+    pub value: V,
+    /// Index of the child giving value, or None if the node is a leaf.
+    pub best_child: Option<usize>,
+    /// Path of child indices followed when both players play their best moves from the node,
+    /// starting with best_child and ending at a leaf.
+    pub principal_variation: Vec<usize>,
+}
+
+/// Solve the subtree rooted at 'current' with the minimax algorithm.
+///
+/// eval gives the value of a leaf for [Player::Max], and turn gives the player to move at an
+/// internal node. The value of an internal node is the greatest value of its childs if Max is to
+/// move, and the lowest one otherwise. When several childs are equally good, the first one is
+/// chosen. The subtree is solved without recursion, so it works on very deep trees.
+///
+/// # Examples
+/// ```
+/// # use libtree::Tree;
+/// use libtree::solve::{minimax, Player};
+///
+/// // each node holds (player to move, score of the leaf)
+/// let mut tree = Tree::from_element((Player::Max, 0));
+/// tree.push_iter(vec![(Player::Min, 0), (Player::Min, 0)]);
+/// tree.navigate_to(0);
+/// tree.push_iter(vec![(Player::Max, 3), (Player::Max, 12)]);
+/// tree.ascend();
+/// tree.navigate_to(1);
+/// tree.push_iter(vec![(Player::Max, 2), (Player::Max, 8)]);
+/// tree.go_to_root();
+///
+/// let solution = minimax(&tree, |node| node.1, |node| node.0);
+/// assert_eq!(solution.value, 3);
+/// assert_eq!(solution.best_child, Some(0));
+/// assert_eq!(solution.principal_variation, vec![0, 0]);
+/// ```
+///
+/// # Panics
+/// This function will panic if called on an empty tree.
+pub fn minimax<T, V, E, P>(tree: &Tree<T>, mut eval: E, mut turn: P) -> Solution<V>
+where
+    V: PartialOrd + Clone,
+    E: FnMut(&T) -> V,
+    P: FnMut(&T) -> Player,
+{
+    if tree.is_empty() {
+        panic!("Tried to call minimax on an empty tree");
+    }
+    let solved = tree.fold_to_tree(|elem, childs: Vec<&(V, Option<usize>)>| {
+        if childs.is_empty() {
+            return (eval(elem), None);
+        }
+        let wanted = match turn(elem) {
+            Player::Max => Ordering::Greater,
+            Player::Min => Ordering::Less,
+        };
+        let best = _best_by(childs.iter().map(|child| &child.0), |value, best| {
+            value.partial_cmp(best) == Some(wanted)
+        });
+        (childs[best].0.clone(), Some(best))
+    });
+    _solution(solved)
+}
+
+/// Solve the subtree rooted at 'current' with the negamax algorithm.
+///
+/// This is the same algorithm as [minimax], but values are always taken from the point of view of
+/// the player to move: eval still gives the value of a leaf for [Player::Max], which is negated
+/// if turn tells that Min is to move at this leaf, and the value of an internal node is the
+/// greatest value of its childs, each one being negated if the other player is to move at this
+/// child. Turns do not need to alternate, so a player can play several moves in a row. The
+/// returned value is therefore the minimax value if Max is to move at 'current', and its negation
+/// otherwise.
+///
+/// # Examples
+/// ```
+/// # use libtree::Tree;
+/// use libtree::solve::{negamax, Player};
+///
+/// // Min is to move at the root, and at the leaves after Max played
+/// let tree: Tree<String> = "(min (max 3 12 8) (max 2 4 6) (max 14 5 2))".parse().unwrap();
+/// let solution = negamax(
+///     &tree,
+///     |leaf| leaf.parse::<i32>().unwrap(),
+///     |node| if node == "max" { Player::Max } else { Player::Min },
+/// );
+/// // Min can hold Max to 6, so the value for Min is -6
+/// assert_eq!(solution.value, -6);
+/// assert_eq!(solution.principal_variation, vec![1, 2]);
+/// ```
+///
+/// # Panics
+/// This function will panic if called on an empty tree.
+pub fn negamax<T, V, E, P>(tree: &Tree<T>, mut eval: E, mut turn: P) -> Solution<V>
+where
+    V: PartialOrd + Clone + Neg<Output = V>,
+    E: FnMut(&T) -> V,
+    P: FnMut(&T) -> Player,
+{
+    if tree.is_empty() {
+        panic!("Tried to call negamax on an empty tree");
+    }
+    // each node is solved as (value for the player to move, best child, player to move)
+    let solved = tree.fold_to_tree(|elem, childs: Vec<&(V, Option<usize>, Player)>| {
+        let player = turn(elem);
+        if childs.is_empty() {
+            return match player {
+                Player::Max => (eval(elem), None, player),
+                Player::Min => (-eval(elem), None, player),
+            };
+        }
+        let values: Vec<V> = childs
+            .iter()
+            .map(|(value, _, child_player)| {
+                if *child_player == player {
+                    value.clone()
+                } else {
+                    -value.clone()
+                }
+            })
+            .collect();
+        let best = _best_by(&values, |value, best| {
+            value.partial_cmp(best) == Some(Ordering::Greater)
+        });
+        (values[best].clone(), Some(best), player)
+    });
+    let solved = solved.map(|(value, best_child, _)| (value, best_child));
+    _solution(solved)
+}
+
+/// Closure choosing the order in which the childs of a node are explored.
+type Order<'a, T> = Box<dyn FnMut(&T, &T) -> Ordering + 'a>;
+
+/// Statistics of an [AlphaBeta] search.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SearchStats {
+    /// Number of nodes entered by the search, 'current' included.
+    pub nodes_visited: usize,
+    /// Number of nodes whose remaining childs were skipped, i.e. pruned with at least one child
+    /// left to explore.
+    pub cutoffs: usize,
+}
+
+/// Minimax search with alpha-beta pruning.
+///
+/// The search is configured with the builder methods, then run either on a tree that is already
+/// built with [AlphaBeta::search], or on a tree grown while searching with
+/// [AlphaBeta::search_expand]. In both cases, only the subtree rooted at 'current' is searched,
+/// without recursion, and the returned [Solution] has the same value as [minimax] would give
+/// (without depth limit). When several childs are equally good, the first one explored is
+/// chosen.
+///
+/// # Examples
+/// ```
+/// # use libtree::Tree;
+/// use libtree::solve::{AlphaBeta, Player};
+///
+/// let tree: Tree<String> = "(max (min 3 12 8) (min 2 4 6) (min 14 5 2))".parse().unwrap();
+/// let mut search = AlphaBeta::new(
+///     |leaf: &String| leaf.parse::<i32>().unwrap(),
+///     |node: &String| if node == "min" { Player::Min } else { Player::Max },
+/// );
+/// let (solution, stats) = search.search(&tree);
+/// assert_eq!(solution.value, 3);
+/// assert_eq!(solution.principal_variation, vec![0, 0]);
+/// // 4 and 6 are never looked at, as 2 already makes the second move worse than the first one
+/// assert_eq!(stats.nodes_visited, 11);
+/// assert_eq!(stats.cutoffs, 1);
+/// ```
+pub struct AlphaBeta<'a, T, V> {
+    eval: Box<dyn FnMut(&T) -> V + 'a>,
+    turn: Box<dyn FnMut(&T) -> Player + 'a>,
+    order: Option<Order<'a, T>>,
+    max_depth: Option<usize>,
+}
+
+impl<'a, T, V> AlphaBeta<'a, T, V>
+where
+    V: PartialOrd + Clone,
+{
+    /// Create a search where eval gives the value of a leaf for [Player::Max], and turn gives the
+    /// player to move at an internal node, as in [minimax]. Childs are explored in the order of
+    /// the tree and the depth is not limited.
+    pub fn new<E, P>(eval: E, turn: P) -> Self
+    where
+        E: FnMut(&T) -> V + 'a,
+        P: FnMut(&T) -> Player + 'a,
+    {
+        AlphaBeta {
+            eval: Box::new(eval),
+            turn: Box::new(turn),
+            order: None,
+            max_depth: None,
+        }
+    }
+
+    /// Explore the childs of each node sorted with compare, the childs comparing as
+    /// [Ordering::Less] first. Exploring the best moves first gives more cutoffs.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// use libtree::solve::{AlphaBeta, Player};
+    ///
+    /// let tree: Tree<i32> = "(0 1 5 3)".parse().unwrap();
+    /// let mut search = AlphaBeta::new(|leaf: &i32| *leaf, |_: &i32| Player::Max)
+    ///     .order(|a: &i32, b: &i32| b.cmp(a));
+    /// let (solution, _) = search.search(&tree);
+    /// // the indices are still the ones of the tree
+    /// assert_eq!(solution.best_child, Some(1));
+    /// ```
+    pub fn order<F>(mut self, compare: F) -> Self
+    where
+        F: FnMut(&T, &T) -> Ordering + 'a,
+    {
+        self.order = Some(Box::new(compare));
+        self
+    }
+
+    /// Stop the search at depth (counted from 'current'), the nodes at this depth being valued
+    /// with eval as if they were leaves.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Search the subtree rooted at 'current'.
+    ///
+    /// # Panics
+    /// This method will panic if called on an empty tree.
+    pub fn search(&mut self, tree: &Tree<T>) -> (Solution<V>, SearchStats) {
+        if tree.is_empty() {
+            panic!("Tried to call search on an empty tree");
+        }
+        self._search(TreeWalker {
+            cursor: tree.cursor(),
+            pending: Vec::new(),
+        })
+    }
+
+    /// Search from 'current', growing the tree as the search goes: the childs of each node
+    /// entered are generated with expand, sorted, and pushed one by one when the search explores
+    /// them, so the moves that are pruned (or beyond the depth limit) are never added to the
+    /// tree. The subtree rooted at 'current' is left as a trace of the search, where the childs of
+    /// a node are in the order they were explored, which is also the order of the indices of the
+    /// returned [Solution].
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// use libtree::solve::{AlphaBeta, Player};
+    ///
+    /// // a game of nim, where players take 1 or 2 sticks, and the one taking the last wins
+    /// let mut tree = Tree::from_element((Player::Max, 4));
+    /// let mut search = AlphaBeta::new(
+    ///     |&(player, _): &(Player, u32)| if player == Player::Max { -1 } else { 1 },
+    ///     |&(player, _): &(Player, u32)| player,
+    /// );
+    /// let (solution, stats) = search.search_expand(&mut tree, |&(player, sticks)| {
+    ///     (1..=sticks.min(2))
+    ///         .map(|taken| (player.opponent(), sticks - taken))
+    ///         .collect()
+    /// });
+    /// // taking one stick leaves a losing position to Min
+    /// assert_eq!(solution.value, 1);
+    /// assert_eq!(solution.best_child, Some(0));
+    /// assert_eq!(tree.peek_child(0), &(Player::Min, 3));
+    /// assert_eq!(tree.subtree_len(), stats.nodes_visited);
+    /// ```
+    ///
+    /// # Panics
+    /// This method will panic if called on an empty tree, or if 'current' already has childs.
+    pub fn search_expand<X>(&mut self, tree: &mut Tree<T>, expand: X) -> (Solution<V>, SearchStats)
+    where
+        X: FnMut(&T) -> Vec<T>,
+    {
+        if tree.is_empty() {
+            panic!("Tried to call search_expand on an empty tree");
+        }
+        if tree.childs_len() > 0 {
+            panic!("Tried to call search_expand on a node with childs");
+        }
+        self._search(ExpandWalker {
+            cursor: tree.cursor_mut(),
+            expand,
+            pending: Vec::new(),
+        })
+    }
+
+    fn _search<W>(&mut self, mut walker: W) -> (Solution<V>, SearchStats)
+    where
+        W: Walker<T>,
+    {
+        let mut stats = SearchStats::default();
+        let mut frames: Vec<Frame<V>> = Vec::new();
+        let (mut alpha, mut beta) = (None, None);
+        loop {
+            // enter the node under the walker
+            stats.nodes_visited += 1;
+            let mut left = None;
+            if self.max_depth == Some(frames.len()) || !walker.open(&mut self.order) {
+                left = Some(((self.eval)(walker.peek()), Vec::new()));
+                if !frames.is_empty() {
+                    walker.ascend();
+                }
+            } else {
+                frames.push(Frame {
+                    player: (self.turn)(walker.peek()),
+                    alpha: alpha.take(),
+                    beta: beta.take(),
+                    child: 0,
+                    best: None,
+                    pv: Vec::new(),
+                    cut: false,
+                });
+            }
+
+            // leave nodes until one has a child left to explore
+            loop {
+                if let Some((value, pv)) = left.take() {
+                    let Some(frame) = frames.last_mut() else {
+                        return (
+                            Solution {
+                                value,
+                                best_child: None,
+                                principal_variation: Vec::new(),
+                            },
+                            stats,
+                        );
+                    };
+                    frame.update(value, pv);
+                    if frame.cut && walker.has_pending() {
+                        stats.cutoffs += 1;
+                    }
+                }
+
+                let frame = frames.last_mut().unwrap();
+                if !frame.cut {
+                    if let Some(index) = walker.descend() {
+                        frame.child = index;
+                        alpha = frame.alpha.clone();
+                        beta = frame.beta.clone();
+                        break;
+                    }
+                }
+                walker.close();
+                let Frame { best, mut pv, .. } = frames.pop().unwrap();
+                // a node is opened only if it has childs, so one of them was explored
+                let (value, best_child) = best.unwrap();
+                pv.push(best_child);
+                if frames.is_empty() {
+                    pv.reverse();
+                    return (
+                        Solution {
+                            value,
+                            best_child: Some(best_child),
+                            principal_variation: pv,
+                        },
+                        stats,
+                    );
+                }
+                walker.ascend();
+                left = Some((value, pv));
+            }
+        }
+    }
+}
+
+/// Node being searched by [AlphaBeta].
+struct Frame<V> {
+    player: Player,
+    alpha: Option<V>,
+    beta: Option<V>,
+    /// Index of the child being explored.
+    child: usize,
+    /// Best value found so far, with the index of the child giving it.
+    best: Option<(V, usize)>,
+    /// Principal variation of the best child, reversed.
+    pv: Vec<usize>,
+    cut: bool,
+}
+
+impl<V> Frame<V>
+where
+    V: PartialOrd + Clone,
+{
+    /// Take into account the value and the reversed principal variation of the child explored.
+    fn update(&mut self, value: V, pv: Vec<usize>) {
+        let better = match (&self.best, self.player) {
+            (None, _) => true,
+            (Some((best, _)), Player::Max) => value > *best,
+            (Some((best, _)), Player::Min) => value < *best,
+        };
+        if !better {
+            return;
+        }
+        match self.player {
+            Player::Max if self.alpha.as_ref().is_none_or(|alpha| value > *alpha) => {
+                self.alpha = Some(value.clone())
+            }
+            Player::Min if self.beta.as_ref().is_none_or(|beta| value < *beta) => {
+                self.beta = Some(value.clone())
+            }
+            _ => (),
+        }
+        self.best = Some((value, self.child));
+        self.pv = pv;
+        if let (Some(alpha), Some(beta)) = (&self.alpha, &self.beta) {
+            self.cut = alpha >= beta;
+        }
+    }
+}
+
+/// Way for [AlphaBeta] to move in the tree searched.
+trait Walker<T> {
+    fn peek(&self) -> &T;
+    /// Prepare the childs of the node under the walker to be explored, returning false if it has
+    /// none.
+    fn open(&mut self, order: &mut Option<Order<'_, T>>) -> bool;
+    /// Go to the next child of the last node opened, returning its index.
+    fn descend(&mut self) -> Option<usize>;
+    /// Return true if the last node opened has childs left to explore.
+    fn has_pending(&self) -> bool;
+    /// Forget the childs left of the last node opened.
+    fn close(&mut self);
+    fn ascend(&mut self);
+}
+
+/// Walker in a tree already built.
+struct TreeWalker<'t, T> {
+    cursor: Cursor<'t, T>,
+    /// Indices of the childs left to explore for each node opened.
+    pending: Vec<std::vec::IntoIter<usize>>,
+}
+
+impl<'t, T> Walker<T> for TreeWalker<'t, T> {
+    fn peek(&self) -> &T {
+        self.cursor.peek()
+    }
+
+    fn open(&mut self, order: &mut Option<Order<'_, T>>) -> bool {
+        let len = self.cursor.childs_len();
+        if len == 0 {
+            return false;
+        }
+        let mut indices: Vec<usize> = (0..len).collect();
+        if let Some(order) = order {
+            let cursor = &self.cursor;
+            indices.sort_by(|a, b| order(cursor.peek_child(*a), cursor.peek_child(*b)));
+        }
+        self.pending.push(indices.into_iter());
+        true
+    }
+
+    fn descend(&mut self) -> Option<usize> {
+        let index = self.pending.last_mut()?.next()?;
+        self.cursor.navigate_to(index);
+        Some(index)
+    }
+
+    fn has_pending(&self) -> bool {
+        self.pending.last().is_some_and(|childs| childs.len() > 0)
+    }
+
+    fn close(&mut self) {
+        self.pending.pop();
+    }
+
+    fn ascend(&mut self) {
+        self.cursor.ascend();
+    }
+}
+
+/// Walker growing the tree with expand.
+struct ExpandWalker<'t, T, X> {
+    cursor: CursorMut<'t, T>,
+    expand: X,
+    /// Childs left to explore for each node opened.
+    pending: Vec<std::vec::IntoIter<T>>,
+}
+
+impl<'t, T, X> Walker<T> for ExpandWalker<'t, T, X>
+where
+    X: FnMut(&T) -> Vec<T>,
+{
+    fn peek(&self) -> &T {
+        self.cursor.peek()
+    }
+
+    fn open(&mut self, order: &mut Option<Order<'_, T>>) -> bool {
+        let mut childs = (self.expand)(self.cursor.peek());
+        if childs.is_empty() {
+            return false;
+        }
+        if let Some(order) = order {
+            childs.sort_by(|a, b| order(a, b));
+        }
+        self.pending.push(childs.into_iter());
+        true
+    }
+
+    fn descend(&mut self) -> Option<usize> {
+        let child = self.pending.last_mut()?.next()?;
+        self.cursor.push(child);
+        let index = self.cursor.childs_len() - 1;
+        self.cursor.navigate_to(index);
+        Some(index)
+    }
+
+    fn has_pending(&self) -> bool {
+        self.pending.last().is_some_and(|childs| childs.len() > 0)
+    }
+
+    fn close(&mut self) {
+        self.pending.pop();
+    }
+
+    fn ascend(&mut self) {
+        self.cursor.ascend();
+    }
+}
+
+/// Kind of a node of a game tree with chance nodes, see [expectiminimax].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    /// Node where [Player::Max] is to move.
+    Max,
+    /// Node where [Player::Min] is to move.
+    Min,
+    /// Node where chance decides of the next position, such as a dice roll or a card draw.
+    Chance,
+}
+
+impl From<Player> for NodeKind {
+    fn from(player: Player) -> Self {
+        match player {
+            Player::Max => NodeKind::Max,
+            Player::Min => NodeKind::Min,
+        }
+    }
+}
+
+/// Value of a node computed by [expectiminimax].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Evaluation {
+    /// Expected value of the node for [Player::Max].
+    pub value: f64,
+    /// Index of the child to choose at a Max or Min node, None at chance nodes and leaves.
+    pub best_child: Option<usize>,
+}
+
+/// Evaluate the subtree rooted at 'current' with the expectiminimax algorithm.
+///
+/// eval gives the value of a leaf for [Player::Max], and kind gives the kind of an internal node.
+/// Max and Min nodes are valued as in [minimax], and a chance node is valued with the expected
+/// value of its childs, probability giving the probability of reaching a child from its father.
+/// The probabilities of the childs of a chance node are not normalized, so they should sum to 1.
+///
+/// The returned tree has the shape of the subtree rooted at 'current', with the [Evaluation] of
+/// each node in place of its element, so the same paths can be followed in both trees to find the
+/// decision to make at any node. 'current' of the returned tree is at its 'root'.
+///
+/// # Examples
+/// ```
+/// # use libtree::Tree;
+/// use libtree::solve::{expectiminimax, NodeKind};
+///
+/// // Max chooses between a sure 4 and a coin flip giving 0 or 10
+/// let tree: Tree<String> = "(max 4 (coin 0 10))".parse().unwrap();
+/// let evaluation = expectiminimax(
+///     &tree,
+///     |leaf| leaf.parse().unwrap(),
+///     |node| if node == "coin" { NodeKind::Chance } else { NodeKind::Max },
+///     |_| 0.5,
+/// );
+/// assert_eq!(evaluation.peek().value, 5.0);
+/// assert_eq!(evaluation.peek().best_child, Some(1));
+/// assert_eq!(evaluation.peek_child(1).best_child, None);
+/// ```
+///
+/// # Panics
+/// This function will panic if called on an empty tree.
+pub fn expectiminimax<T, E, K, P>(
+    tree: &Tree<T>,
+    mut eval: E,
+    mut kind: K,
+    mut probability: P,
+) -> Tree<Evaluation>
+where
+    E: FnMut(&T) -> f64,
+    K: FnMut(&T) -> NodeKind,
+    P: FnMut(&T) -> f64,
+{
+    if tree.is_empty() {
+        panic!("Tried to call expectiminimax on an empty tree");
+    }
+    let unknown = Evaluation {
+        value: 0.0,
+        best_child: None,
+    };
+    let mut cursor = tree.cursor();
+    let mut evaluations = Tree::from_element(unknown);
+    // index of the next child to explore for each node from 'current' to the cursor
+    let mut next = vec![0];
+    while let Some(index) = next.last_mut() {
+        if *index < cursor.childs_len() {
+            cursor.navigate_to(*index);
+            evaluations.push(unknown);
+            evaluations.navigate_to(*index);
+            *index += 1;
+            next.push(0);
+            continue;
+        }
+
+        // every child of the cursor is evaluated
+        let childs = evaluations.iter_childs().map(|child| child.value);
+        let evaluation = if cursor.childs_len() == 0 {
+            Evaluation {
+                value: eval(cursor.peek()),
+                best_child: None,
+            }
+        } else {
+            match kind(cursor.peek()) {
+                NodeKind::Max => _best_evaluation(childs, |value, best| value > best),
+                NodeKind::Min => _best_evaluation(childs, |value, best| value < best),
+                NodeKind::Chance => Evaluation {
+                    value: childs
+                        .enumerate()
+                        .map(|(index, value)| probability(cursor.peek_child(index)) * value)
+                        .sum(),
+                    best_child: None,
+                },
+            }
+        };
+        *evaluations.peek_mut() = evaluation;
+        next.pop();
+        if !next.is_empty() {
+            cursor.ascend();
+            evaluations.ascend();
+        }
+    }
+    evaluations
+}
+
+/// Return the evaluation of the first value better than the values before it.
+fn _best_evaluation<I, F>(values: I, better: F) -> Evaluation
+where
+    I: Iterator<Item = f64>,
+    F: Fn(f64, f64) -> bool,
+{
+    let mut best = Evaluation {
+        value: 0.0,
+        best_child: None,
+    };
+    for (index, value) in values.enumerate() {
+        if best.best_child.is_none() || better(value, best.value) {
+            best = Evaluation {
+                value,
+                best_child: Some(index),
+            };
+        }
+    }
+    best
+}
+
+/// Return the index of the first value which is better than all the values before it.
+fn _best_by<'v, V, I, F>(values: I, mut better: F) -> usize
+where
+    V: 'v,
+    I: IntoIterator<Item = &'v V>,
+    F: FnMut(&V, &V) -> bool,
+{
+    let mut values = values.into_iter().enumerate();
+    let Some((_, mut best_value)) = values.next() else {
+        return 0;
+    };
+    let mut best = 0;
+    for (index, value) in values {
+        if better(value, best_value) {
+            best = index;
+            best_value = value;
+        }
+    }
+    best
+}
+
+/// Build the solution from the tree of (value, best child) of every node.
+fn _solution<V>(solved: Tree<(V, Option<usize>)>) -> Solution<V>
+where
+    V: Clone,
+{
+    let mut cursor = solved.cursor();
+    let (value, best_child) = cursor.peek().clone();
+    let mut principal_variation = Vec::new();
+    while let Some(index) = cursor.peek().1 {
+        principal_variation.push(index);
+        cursor.navigate_to(index);
+    }
+    Solution {
+        value,
+        best_child,
+        principal_variation,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Each node holds the player to move and a score, which is only used on leaves.
+    type Node = (Player, i32);
+
+    fn eval(node: &Node) -> i32 {
+        node.1
+    }
+
+    fn turn(node: &Node) -> Player {
+        node.0
+    }
+
+    /// Build a complete tree of the given depth and branching factor, players alternating from
+    /// Max at the root, leaves getting pseudo random scores.
+    fn game(depth: usize, branching: usize) -> Tree<Node> {
+        let mut tree = Tree::from_element((Player::Max, 0));
+        let mut seed: i32 = 17;
+        let mut stack = vec![(0, 0)];
+        while let Some((level, next)) = stack.last_mut() {
+            if *level == depth || *next == branching {
+                stack.pop();
+                if !stack.is_empty() {
+                    tree.ascend();
+                }
+                continue;
+            }
+            *next += 1;
+            let level = *level + 1;
+            seed = (seed * 31 + 7) % 101;
+            let player = tree.peek().0.opponent();
+            tree.push((player, seed - 50));
+            tree.navigate_to(tree.childs_len() - 1);
+            stack.push((level, 0));
+        }
+        tree
+    }
+
+    /// Plain recursive minimax, as a reference.
+    fn reference(tree: &mut Tree<Node>) -> i32 {
+        if tree.childs_len() == 0 {
+            return eval(tree.peek());
+        }
+        let mut values = Vec::new();
+        for index in 0..tree.childs_len() {
+            tree.navigate_to(index);
+            values.push(reference(tree));
+            tree.ascend();
+        }
+        match turn(tree.peek()) {
+            Player::Max => values.into_iter().max().unwrap(),
+            Player::Min => values.into_iter().min().unwrap(),
+        }
+    }
+
+    #[test]
+    fn minimax_and_negamax() {
+        let mut tree = game(5, 3);
+        let expected = reference(&mut tree);
+        let solution = minimax(&tree, eval, turn);
+        assert_eq!(solution.value, expected);
+        assert_eq!(solution.principal_variation.len(), 5);
+        assert_eq!(solution.best_child, Some(solution.principal_variation[0]));
+        assert_eq!(negamax(&tree, eval, turn), solution);
+
+        // following the principal variation leads to a leaf of the same value
+        tree.navigate_path(&solution.principal_variation);
+        assert_eq!(eval(tree.peek()), expected);
+
+        // Max plays twice in a row on the first move
+        let mut extra = Tree::from_element((Player::Max, 0));
+        extra.push_iter(vec![(Player::Max, 0), (Player::Min, 0)]);
+        extra.navigate_to(0);
+        extra.push_iter(vec![(Player::Min, 1), (Player::Min, 2)]);
+        extra.go_to_root();
+        let solution = minimax(&extra, eval, turn);
+        assert_eq!(solution.value, 2);
+        assert_eq!(solution.principal_variation, vec![0, 1]);
+        assert_eq!(negamax(&extra, eval, turn), solution);
+
+        // from a Min node, negamax gives the opposite value
+        tree.go_to_root();
+        tree.navigate_to(1);
+        let expected = reference(&mut tree);
+        assert_eq!(minimax(&tree, eval, turn).value, expected);
+        assert_eq!(negamax(&tree, eval, turn).value, -expected);
+    }
+
+    #[test]
+    fn leaf_and_ties() {
+        let tree = Tree::from_element((Player::Max, 4));
+        let solution = minimax(&tree, eval, turn);
+        assert_eq!(solution.value, 4);
+        assert_eq!(solution.best_child, None);
+        assert!(solution.principal_variation.is_empty());
+
+        let mut tree = Tree::from_element((Player::Min, 0));
+        tree.push_iter(vec![(Player::Max, 1), (Player::Max, 1), (Player::Max, 0)]);
+        assert_eq!(minimax(&tree, eval, turn).best_child, Some(2));
+        tree.navigate_to(2);
+        *tree.peek_mut() = (Player::Max, 1);
+        tree.ascend();
+        assert_eq!(minimax(&tree, eval, turn).best_child, Some(0));
+    }
+
+    #[test]
+    fn deep() {
+        let depth = 100_000;
+        let mut tree = Tree::from_element((Player::Max, 0));
+        for _ in 0..depth {
+            let player = tree.peek().0.opponent();
+            tree.push_iter(vec![(player, 1), (player, 0)]);
+            tree.navigate_to(1);
+        }
+        tree.go_to_root();
+        let solution = minimax(&tree, eval, turn);
+        assert_eq!(negamax(&tree, eval, turn), solution);
+        tree.navigate_path(&solution.principal_variation);
+        assert_eq!(tree.childs_len(), 0);
+        assert_eq!(eval(tree.peek()), solution.value);
+    }
+
+    #[test]
+    fn alpha_beta() {
+        let mut tree = game(6, 3);
+        let expected = minimax(&tree, eval, turn);
+        let (solution, stats) = AlphaBeta::new(eval, turn).search(&tree);
+        assert_eq!(solution, expected);
+        assert!(stats.cutoffs > 0);
+
+        // a cut on the last child skips nothing
+        let mut last = Tree::from_element((Player::Max, 0));
+        last.push_iter(vec![(Player::Min, 3), (Player::Min, 0)]);
+        last.navigate_to(1);
+        last.push_iter(vec![(Player::Max, 5), (Player::Max, 2)]);
+        last.go_to_root();
+        let (_, stats) = AlphaBeta::new(eval, turn).search(&last);
+        assert_eq!(stats.nodes_visited, 5);
+        assert_eq!(stats.cutoffs, 0);
+        assert!(stats.nodes_visited < tree.subtree_len());
+
+        // exploring the best leaves first
+        let mut search = AlphaBeta::new(eval, turn).order(|a: &Node, b: &Node| match a.0 {
+            Player::Max => a.1.cmp(&b.1),
+            Player::Min => b.1.cmp(&a.1),
+        });
+        let (solution, _) = search.search(&tree);
+        assert_eq!(solution.value, expected.value);
+        tree.navigate_path(&solution.principal_variation);
+        assert_eq!(tree.childs_len(), 0);
+        assert_eq!(eval(tree.peek()), expected.value);
+
+        tree.go_to_root();
+        let (solution, stats) = AlphaBeta::new(eval, turn).max_depth(0).search(&tree);
+        assert_eq!(solution.value, 0);
+        assert_eq!(solution.best_child, None);
+        assert_eq!(stats.nodes_visited, 1);
+    }
+
+    /// Childs of a position of a game of nim where players take 1, 2 or 3 sticks.
+    fn nim(&(player, sticks): &Node) -> Vec<Node> {
+        (1..=sticks.min(3))
+            .map(|taken| (player.opponent(), sticks - taken))
+            .collect()
+    }
+
+    /// Value of a position of nim with no sticks left: the player to move lost.
+    fn nim_eval(&(player, _): &Node) -> i32 {
+        match player {
+            Player::Max => -1,
+            Player::Min => 1,
+        }
+    }
+
+    #[test]
+    fn alpha_beta_expand() {
+        let mut full = Tree::from_element((Player::Max, 9));
+        let mut stack = vec![0];
+        while let Some(next) = stack.last_mut() {
+            let childs = nim(full.peek());
+            if *next == childs.len() {
+                stack.pop();
+                if !stack.is_empty() {
+                    full.ascend();
+                }
+                continue;
+            }
+            full.push(childs[*next]);
+            *next += 1;
+            full.navigate_to(full.childs_len() - 1);
+            stack.push(0);
+        }
+        let expected = minimax(&full, nim_eval, turn);
+
+        let mut tree = Tree::from_element((Player::Max, 9));
+        let (solution, stats) = AlphaBeta::new(nim_eval, turn).search_expand(&mut tree, nim);
+        assert_eq!(solution.value, expected.value);
+        assert_eq!(solution.best_child, expected.best_child);
+        assert_eq!(tree.subtree_len(), stats.nodes_visited);
+        assert!(stats.nodes_visited < full.subtree_len());
+        assert_eq!(tree.peek(), &(Player::Max, 9));
+        tree.navigate_path(&solution.principal_variation);
+        assert_eq!(tree.peek().1, 0);
+
+        let mut tree = Tree::from_element((Player::Max, 9));
+        let (_, stats) = AlphaBeta::new(nim_eval, turn)
+            .max_depth(2)
+            .search_expand(&mut tree, nim);
+        assert_eq!(tree.height(), 2);
+        assert_eq!(tree.subtree_len(), stats.nodes_visited);
+    }
+
+    #[test]
+    fn alpha_beta_deep() {
+        let depth = 100_000;
+        let mut tree = Tree::from_element((Player::Max, depth));
+        let (solution, stats) =
+            AlphaBeta::new(eval, turn).search_expand(&mut tree, |node| match node.1 {
+                0 => vec![],
+                n => vec![(node.0.opponent(), n - 1)],
+            });
+        assert_eq!(solution.value, 0);
+        assert_eq!(solution.principal_variation.len(), depth as usize);
+        assert_eq!(stats.nodes_visited, depth as usize + 1);
+        assert_eq!(tree.height(), depth as usize);
+    }
+
+    #[test]
+    #[should_panic(expected = "Tried to call search_expand on a node with childs")]
+    fn alpha_beta_expand_childs() {
+        let mut tree = Tree::from_element((Player::Max, 3));
+        tree.push((Player::Min, 2));
+        AlphaBeta::new(eval, turn).search_expand(&mut tree, nim);
+    }
+
+    #[test]
+    fn expectiminimax_values() {
+        // without chance nodes, expectiminimax is minimax
+        let tree = game(5, 3);
+        let expected = minimax(&tree, eval, turn);
+        let evaluations = expectiminimax(
+            &tree,
+            |node| node.1 as f64,
+            |node| node.0.into(),
+            |_| panic!("no chance node"),
+        );
+        assert_eq!(evaluations.peek().value, expected.value as f64);
+        assert_eq!(evaluations.peek().best_child, expected.best_child);
+        assert_eq!(evaluations.subtree_len(), tree.subtree_len());
+        let mut cursor = evaluations.cursor();
+        cursor.navigate_path(&expected.principal_variation[..1]);
+        assert_eq!(
+            cursor.peek().best_child,
+            Some(expected.principal_variation[1])
+        );
+
+        // Min can take 2, or roll a die giving 6 on a one and 0 otherwise, after which Max
+        // chooses between the outcome and 1
+        let mut tree = Tree::from_element((NodeKind::Min, 0.0));
+        tree.push_iter(vec![(NodeKind::Max, 2.0), (NodeKind::Chance, 0.0)]);
+        tree.navigate_to(1);
+        tree.push_iter(vec![(NodeKind::Max, 1.0 / 6.0), (NodeKind::Max, 5.0 / 6.0)]);
+        tree.navigate_to(0);
+        tree.push_iter(vec![(NodeKind::Min, 6.0), (NodeKind::Min, 1.0)]);
+        tree.ascend();
+        tree.navigate_to(1);
+        tree.push_iter(vec![(NodeKind::Min, 0.0), (NodeKind::Min, 1.0)]);
+        tree.go_to_root();
+        // the leaves hold their value, and the childs of the chance node their probability
+        let evaluations = expectiminimax(&tree, |node| node.1, |node| node.0, |node| node.1);
+        let mut cursor = evaluations.cursor();
+        assert_eq!(cursor.peek().best_child, Some(1));
+        assert!((cursor.peek().value - (6.0 / 6.0 + 5.0 / 6.0)).abs() < 1e-9);
+        cursor.navigate_to(1);
+        assert_eq!(cursor.peek().best_child, None);
+        cursor.navigate_to(1);
+        assert_eq!(cursor.peek().best_child, Some(1));
+
+        // from a node which is not 'root'
+        tree.navigate_path(&[1, 0]);
+        let evaluations = expectiminimax(&tree, |node| node.1, |node| node.0, |node| node.1);
+        assert_eq!(evaluations.peek().value, 6.0);
+        assert_eq!(evaluations.subtree_len(), 3);
+    }
+
+    #[test]
+    fn expectiminimax_deep() {
+        let depth = 100_000;
+        let mut tree = Tree::from_element(NodeKind::Chance);
+        for _ in 0..depth {
+            tree.push_iter([NodeKind::Max, NodeKind::Chance]);
+            tree.navigate_to(1);
+        }
+        tree.go_to_root();
+        let evaluations = expectiminimax(&tree, |_| 1.0, |kind| *kind, |_| 0.5);
+        assert_eq!(evaluations.peek().value, 1.0);
+        assert_eq!(evaluations.height(), depth);
+    }
+
+    #[test]
+    #[should_panic(expected = "Tried to call minimax on an empty tree")]
+    fn empty() {
+        minimax(&Tree::<Node>::default(), eval, turn);
+    }
+}
@@ -0,0 +1,193 @@
+use crate::tree::{ChildLink, Link};
+
+/// Report on the shape of a subtree, returned by [crate::Tree::stats] and the `stats` methods of
+/// the cursors.
+///
+/// The branching factor of a node is its number of childs. It is only computed over internal
+/// nodes (nodes with at least one child), so that leaves do not drag the mean down. If the subtree
+/// has no internal node, the three branching fields are 0.
+///
+/// # Examples
+/// ```
+/// # use libtree::Tree;
+/// let mut tree = Tree::from_element(0);
+/// tree.push_iter(vec![1, 2, 3]);
+/// tree.navigate_to(0);
+/// tree.push(4);
+/// tree.go_to_root();
+/// let stats = tree.stats();
+/// assert_eq!(stats.nodes_per_level, vec![1, 3, 1]);
+/// assert_eq!(stats.leaf_count, 3);
+/// assert_eq!(stats.min_branching, 1);
+/// assert_eq!(stats.max_branching, 3);
+/// assert_eq!(stats.mean_branching, 2.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeStats {
+    /// Number of nodes at each depth, 'current' being at index 0.
+    pub nodes_per_level: Vec<usize>,
+    /// Number of nodes without childs.
+    pub leaf_count: usize,
+    /// Smallest number of childs of an internal node.
+    pub min_branching: usize,
+    /// Largest number of childs of a node.
+    pub max_branching: usize,
+    /// Mean number of childs of the internal nodes.
+    pub mean_branching: f64,
+}
+
+impl TreeStats {
+    /// Compute the stats of the subtree rooted at start (all empty if start is None).
+    pub(crate) fn new<T>(start: Link<T>) -> Self {
+        let mut stats = TreeStats {
+            nodes_per_level: Vec::new(),
+            leaf_count: 0,
+            min_branching: 0,
+            max_branching: 0,
+            mean_branching: 0.0,
+        };
+        let mut internal = 0;
+        let mut edges = 0;
+        let mut stack: Vec<(ChildLink<T>, usize)> =
+            start.map(|link| (link, 0)).into_iter().collect();
+        while let Some((link, level)) = stack.pop() {
+            if stats.nodes_per_level.len() == level {
+                stats.nodes_per_level.push(0);
+            }
+            stats.nodes_per_level[level] += 1;
+
+            let childs = unsafe { &(*link.as_ptr()).childs };
+            if childs.is_empty() {
+                stats.leaf_count += 1;
+                continue;
+            }
+            if internal == 0 || childs.len() < stats.min_branching {
+                stats.min_branching = childs.len();
+            }
+            stats.max_branching = stats.max_branching.max(childs.len());
+            internal += 1;
+            edges += childs.len();
+            stack.extend(childs.iter().map(|child| (*child, level + 1)));
+        }
+        if internal > 0 {
+            stats.mean_branching = edges as f64 / internal as f64;
+        }
+        stats
+    }
+
+    /// Return the total number of nodes in the subtree.
+    pub fn len(&self) -> usize {
+        self.nodes_per_level.iter().sum()
+    }
+
+    /// Return true if the stats were computed on an empty tree.
+    pub fn is_empty(&self) -> bool {
+        self.nodes_per_level.is_empty()
+    }
+
+    /// Return the height of the subtree (0 if it is empty or a single node).
+    pub fn height(&self) -> usize {
+        self.nodes_per_level.len().saturating_sub(1)
+    }
+}
+
+/// Return the number of nodes in the subtree rooted at start.
+pub(crate) fn _subtree_len<T>(start: Link<T>) -> usize {
+    let mut stack: Vec<ChildLink<T>> = start.into_iter().collect();
+    let mut len = 0;
+    while let Some(link) = stack.pop() {
+        len += 1;
+        stack.extend(unsafe { (*link.as_ptr()).childs.iter() });
+    }
+    len
+}
+
+/// Return the number of leaves in the subtree rooted at start.
+pub(crate) fn _leaf_count<T>(start: Link<T>) -> usize {
+    let mut stack: Vec<ChildLink<T>> = start.into_iter().collect();
+    let mut count = 0;
+    while let Some(link) = stack.pop() {
+        let childs = unsafe { &(*link.as_ptr()).childs };
+        if childs.is_empty() {
+            count += 1;
+        }
+        stack.extend(childs.iter());
+    }
+    count
+}
+
+/// Return the length of the longest path from start to a leaf of its subtree.
+pub(crate) fn _height<T>(start: Link<T>) -> usize {
+    let mut stack: Vec<(ChildLink<T>, usize)> = start.map(|link| (link, 0)).into_iter().collect();
+    let mut height = 0;
+    while let Some((link, depth)) = stack.pop() {
+        height = height.max(depth);
+        stack.extend(unsafe { (*link.as_ptr()).childs.iter() }.map(|child| (*child, depth + 1)));
+    }
+    height
+}
+
+/// Return the number of ancestors of start.
+pub(crate) fn _depth<T>(start: Link<T>) -> usize {
+    let mut depth = 0;
+    let mut link = start.and_then(|link| unsafe { (*link.as_ptr()).father });
+    while let Some(father) = link {
+        depth += 1;
+        link = unsafe { (*father.as_ptr()).father };
+    }
+    depth
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Tree;
+
+    #[test]
+    fn metrics() {
+        let mut tree = Tree::from_element(0);
+        tree.push_iter(vec![1, 2, 3]);
+        tree.navigate_to(1);
+        tree.push_iter(vec![4, 5]);
+        tree.navigate_to(0);
+        tree.push(6);
+        tree.go_to_root();
+
+        assert_eq!(tree.subtree_len(), 7);
+        assert_eq!(tree.leaf_count(), 4);
+        assert_eq!(tree.height(), 3);
+        assert_eq!(tree.depth(), 0);
+
+        tree.navigate_path(&[1, 0]);
+        assert_eq!(tree.subtree_len(), 2);
+        assert_eq!(tree.leaf_count(), 1);
+        assert_eq!(tree.height(), 1);
+        assert_eq!(tree.depth(), 2);
+
+        let cursor = tree.cursor_root();
+        let stats = cursor.stats();
+        assert_eq!(stats.nodes_per_level, vec![1, 3, 2, 1]);
+        assert_eq!(stats.len(), 7);
+        assert_eq!(stats.height(), 3);
+        assert_eq!(stats.leaf_count, 4);
+        assert_eq!(stats.min_branching, 1);
+        assert_eq!(stats.max_branching, 3);
+        assert_eq!(stats.mean_branching, 2.0);
+    }
+
+    #[test]
+    fn metrics_on_empty_and_single() {
+        let tree: Tree<i32> = Tree::default();
+        assert_eq!(tree.subtree_len(), 0);
+        assert_eq!(tree.leaf_count(), 0);
+        assert_eq!(tree.height(), 0);
+        assert_eq!(tree.depth(), 0);
+        assert!(tree.stats().is_empty());
+
+        let tree = Tree::from_element(1);
+        let stats = tree.stats();
+        assert_eq!(stats.nodes_per_level, vec![1]);
+        assert_eq!(stats.leaf_count, 1);
+        assert_eq!(stats.max_branching, 0);
+        assert_eq!(stats.mean_branching, 0.0);
+    }
+}
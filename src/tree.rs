@@ -1,8 +1,19 @@
-use crate::{Cursor, CursorMut, UnsafeCursor};
+use crate::iter::{
+    BfsIterator, BfsIteratorMut, IntoIter, LevelIterator, PostOrderIterator, PostOrderIteratorMut,
+    ScanIterator,
+};
+use crate::stats::{_depth, _height, _leaf_count, _subtree_len};
+use crate::{Cursor, CursorMut, Dot, NodeHandle, Render, TreeError, TreeStats, UnsafeCursor};
+use std::cell::OnceCell;
+use std::cmp::Ordering;
 use std::collections::LinkedList;
-use std::convert::Into;
+use std::convert::Infallible;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io;
 use std::marker::PhantomData;
 use std::ptr::NonNull;
+use std::rc::Rc;
 
 /// Represent a potential pointer to another Node
 pub type Link<T> = Option<NonNull<Node<T>>>;
@@ -16,6 +27,8 @@ pub(crate) struct Node<T> {
     pub father: Link<T>,
     pub childs: Vec<ChildLink<T>>,
     pub elem: T,
+    /// Token created the first time a [NodeHandle] is taken on this node, and freed with it.
+    pub token: OnceCell<Rc<()>>,
 }
 
 /// The main structure in this tree crate
@@ -39,6 +52,26 @@ pub(crate) struct Node<T> {
 /// This is due to the fact that in order to move around the tree, you need to change the 'current' pointer of the tree and therefore
 /// invalidating every normal references to the tree. Check [Cursor], [CursorMut] and [UnsafeCursor]
 /// for more detail.
+///
+/// ## Comparison
+/// Two trees are equal if they have the same shape and the same elements, that is if their
+/// pre-order sequences of (elem, number of childs) pairs from 'root' are equal. 'current' does
+/// not participate: two trees differing only by the position of 'current' are equal (and hash
+/// the same). [Ord] compares these same sequences lexicographically, so an empty tree is lower
+/// than any non-empty tree, and at the first node where the trees differ, elements are compared
+/// before the number of childs.
+///
+/// ```
+/// # use libtree::Tree;
+/// let mut a = Tree::from_element(0);
+/// a.push_iter(vec![1, 2]);
+/// let mut b = a.clone();
+/// b.navigate_to(1);
+/// assert_eq!(a, b);
+/// b.push(3);
+/// assert!(a < b);
+/// assert_eq!(format!("{:?}", b), "Tree(0 [1, 2 [3]])");
+/// ```
 pub struct Tree<T> {
     root: Link<T>,
     current: Link<T>,
@@ -52,6 +85,7 @@ impl<T> Tree<T> {
             NonNull::new_unchecked(Box::into_raw(Box::new(Node {
                 elem: el,
                 childs: Vec::new(),
+                token: OnceCell::new(),
                 father: None,
             })))
         };
@@ -78,6 +112,28 @@ impl<T> Tree<T> {
         self.root.is_none()
     }
 
+    /// Return 'current', or [TreeError::EmptyTree] if the tree is empty.
+    fn current_link(&self) -> Result<ChildLink<T>, TreeError> {
+        self.current.ok_or(TreeError::EmptyTree)
+    }
+
+    /// Build a tree from already linked nodes. root must have no father, and current must belong
+    /// to the tree rooted at root.
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_links(root: Link<T>, current: Link<T>) -> Self {
+        Tree {
+            root,
+            current,
+            _boo: PhantomData,
+        }
+    }
+
+    /// Return ('root', 'current').
+    #[cfg(feature = "serde")]
+    pub(crate) fn links(&self) -> (Link<T>, Link<T>) {
+        (self.root, self.current)
+    }
+
     /// Push el to 'current'.child as a new node in the tree.
     ///
     /// # Examples
@@ -99,11 +155,26 @@ impl<T> Tree<T> {
                 .push(NonNull::new_unchecked(Box::into_raw(Box::new(Node {
                     elem: el,
                     childs: Vec::new(),
+                    token: OnceCell::new(),
                     father: self.current,
                 }))))
         }
     }
 
+    /// Same as [Tree::push], but returns [TreeError::EmptyTree] instead of panicking.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::{Tree, TreeError};
+    /// let mut tree = Tree::default();
+    /// assert_eq!(tree.try_push(1), Err(TreeError::EmptyTree));
+    /// ```
+    pub fn try_push(&mut self, el: T) -> Result<(), TreeError> {
+        self.current_link()?;
+        self.push(el);
+        Ok(())
+    }
+
     /// Convenient method to push the elements of an iterator into the tree.
     /// It's litteraly : for el in iter.into_iter() { tree.push(el) }
     ///
@@ -146,12 +217,36 @@ impl<T> Tree<T> {
                 NonNull::new_unchecked(Box::into_raw(Box::new(Node {
                     elem: el,
                     childs: Vec::new(),
+                    token: OnceCell::new(),
                     father: self.current,
                 }))),
             );
         }
     }
 
+    /// Same as [Tree::insert], but returns an error instead of panicking if the tree is empty or
+    /// if index > tree.childs_len().
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::{Tree, TreeError};
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push(1);
+    /// assert_eq!(tree.try_insert(1, 2), Ok(()));
+    /// assert_eq!(
+    ///     tree.try_insert(5, 3),
+    ///     Err(TreeError::ChildOutOfRange { index: 5, len: 2 })
+    /// );
+    /// ```
+    pub fn try_insert(&mut self, index: usize, el: T) -> Result<(), TreeError> {
+        let len = self.try_childs_len()?;
+        if index > len {
+            return Err(TreeError::ChildOutOfRange { index, len });
+        }
+        self.insert(index, el);
+        Ok(())
+    }
+
     /// Set current to 'current'.childs\[index\], therefore navigating current to it's idx childs.
     ///
     /// # Panic
@@ -173,6 +268,34 @@ impl<T> Tree<T> {
         self.current = Some(current_node.childs[index]);
     }
 
+    /// Same as [Tree::navigate_to], but returns an error instead of panicking.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::{Tree, TreeError};
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push(1);
+    /// assert_eq!(
+    ///     tree.try_navigate_to(1),
+    ///     Err(TreeError::ChildOutOfRange { index: 1, len: 1 })
+    /// );
+    /// assert_eq!(tree.try_navigate_to(0), Ok(()));
+    /// assert_eq!(tree.peek(), &1);
+    /// ```
+    pub fn try_navigate_to(&mut self, index: usize) -> Result<(), TreeError> {
+        let current_node = unsafe { &*(self.current_link()?.as_ptr()) };
+        match current_node.childs.get(index) {
+            Some(child) => {
+                self.current = Some(*child);
+                Ok(())
+            }
+            None => Err(TreeError::ChildOutOfRange {
+                index,
+                len: current_node.childs.len(),
+            }),
+        }
+    }
+
     /// Set current to 'current'.father, therefore naviguating current to it's father
     ///
     /// # Examples
@@ -201,6 +324,23 @@ impl<T> Tree<T> {
         self.current = current_node.father;
     }
 
+    /// Same as [Tree::ascend], but returns an error instead of panicking.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::{Tree, TreeError};
+    /// let mut tree = Tree::from_element(0);
+    /// assert_eq!(tree.try_ascend(), Err(TreeError::NoFather));
+    /// ```
+    pub fn try_ascend(&mut self) -> Result<(), TreeError> {
+        let current_node = unsafe { &(*self.current_link()?.as_ptr()) };
+        if current_node.father.is_none() {
+            return Err(TreeError::NoFather);
+        }
+        self.current = current_node.father;
+        Ok(())
+    }
+
     /// Return true if current has a father. Note that it will return false and not panic is tree
     /// is empty.
     ///
@@ -241,6 +381,118 @@ impl<T> Tree<T> {
         self.current = self.root;
     }
 
+    /// Same as [Tree::go_to_root], but returns [TreeError::EmptyTree] instead of panicking.
+    pub fn try_go_to_root(&mut self) -> Result<(), TreeError> {
+        self.current_link()?;
+        self.current = self.root;
+        Ok(())
+    }
+
+    /// Return the position of 'current' in the childs of its father, or None if 'current' has no
+    /// father.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// assert_eq!(tree.child_index(), None);
+    /// tree.navigate_to(1);
+    /// assert_eq!(tree.child_index(), Some(1));
+    /// ```
+    pub fn child_index(&self) -> Option<usize> {
+        if self.is_empty() {
+            panic!("Tried to call child_index on an empty tree");
+        }
+        _child_index(self.current.unwrap())
+    }
+
+    /// Return the path from 'root' to 'current', i.e. the list of child indices to navigate to
+    /// from 'root' in order to reach 'current'. This is linear in the depth of 'current' times
+    /// the number of childs of each of its ancestors.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// tree.navigate_to(1);
+    /// tree.push_iter(vec![3, 4]);
+    /// tree.navigate_to(1);
+    /// let path = tree.path();
+    /// assert_eq!(path, vec![1, 1]);
+    /// tree.go_to_root();
+    /// tree.navigate_path(&path);
+    /// assert_eq!(tree.peek(), &4);
+    /// ```
+    pub fn path(&self) -> Vec<usize> {
+        if self.is_empty() {
+            panic!("Tried to call path on an empty tree");
+        }
+        _path(self.current.unwrap())
+    }
+
+    /// Navigate 'current' successively to each child index of path, starting from 'current'.
+    /// Calling it from 'root' with the output of [Tree::path] brings back to the same node.
+    ///
+    /// # Panics
+    /// This method will panic if one of the indices is out of range. In this case 'current' is
+    /// left unchanged.
+    pub fn navigate_path(&mut self, path: &[usize]) {
+        if let Err(err) = self.try_navigate_path(path) {
+            panic!("Tried to navigate along path {:?}, but {}", path, err);
+        }
+    }
+
+    /// Same as [Tree::navigate_path], but returns an error instead of panicking. 'current' is
+    /// left unchanged if an error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::{Tree, TreeError};
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// assert_eq!(
+    ///     tree.try_navigate_path(&[1, 0]),
+    ///     Err(TreeError::ChildOutOfRange { index: 0, len: 0 })
+    /// );
+    /// assert_eq!(tree.peek(), &0);
+    /// ```
+    pub fn try_navigate_path(&mut self, path: &[usize]) -> Result<(), TreeError> {
+        self.current = Some(_follow_path(self.current_link()?, path)?);
+        Ok(())
+    }
+
+    /// Ascend 'current' n times.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push(1);
+    /// tree.navigate_to(0);
+    /// tree.push(2);
+    /// tree.navigate_to(0);
+    /// tree.ascend_n(2);
+    /// assert_eq!(tree.peek(), &0);
+    /// ```
+    ///
+    /// # Panics
+    /// This method will panic if 'current' has less than n ancestors. In this case 'current' is
+    /// left unchanged.
+    pub fn ascend_n(&mut self, n: usize) {
+        if let Err(err) = self.try_ascend_n(n) {
+            panic!("Tried to ascend {} times, but {}", n, err);
+        }
+    }
+
+    /// Same as [Tree::ascend_n], but returns an error instead of panicking. 'current' is left
+    /// unchanged if an error is returned.
+    pub fn try_ascend_n(&mut self, n: usize) -> Result<(), TreeError> {
+        self.current = Some(_ascend_n(self.current_link()?, n)?);
+        Ok(())
+    }
+
     /// Peek at 'current', returning a reference to the element stored in 'current'
     ///
     /// # Examples
@@ -256,6 +508,19 @@ impl<T> Tree<T> {
         unsafe { &(*self.current.unwrap().as_ptr()).elem }
     }
 
+    /// Same as [Tree::peek], but returns None instead of panicking if the tree is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let tree = Tree::from_element(32);
+    /// assert_eq!(tree.peek_opt(), Some(&32));
+    /// assert_eq!(Tree::<i32>::default().peek_opt(), None);
+    /// ```
+    pub fn peek_opt(&self) -> Option<&T> {
+        self.current.map(|link| unsafe { &(*link.as_ptr()).elem })
+    }
+
     /// Same as [Tree::peek], but returns a mutable reference instead
     ///
     /// # Examples
@@ -271,6 +536,11 @@ impl<T> Tree<T> {
         unsafe { &mut (*self.current.unwrap().as_ptr()).elem }
     }
 
+    /// Same as [Tree::peek_mut], but returns None instead of panicking if the tree is empty.
+    pub fn peek_mut_opt(&mut self) -> Option<&mut T> {
+        self.current.map(|link| unsafe { &mut (*link.as_ptr()).elem })
+    }
+
     /// Peek on 'current'.childs\[index\]
     ///
     /// # Examples
@@ -296,7 +566,45 @@ impl<T> Tree<T> {
             );
         }
 
-        unsafe { &(*(*self.current.unwrap().as_ptr()).childs[index].as_ptr()).elem }
+        unsafe { &(*(&(*self.current.unwrap().as_ptr()).childs)[index].as_ptr()).elem }
+    }
+
+    /// Same as [Tree::peek_child], but returns an error instead of panicking.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::{Tree, TreeError};
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2, 3]);
+    /// assert_eq!(tree.try_peek_child(2), Ok(&3));
+    /// assert_eq!(
+    ///     tree.try_peek_child(3),
+    ///     Err(TreeError::ChildOutOfRange { index: 3, len: 3 })
+    /// );
+    /// ```
+    pub fn try_peek_child(&self, index: usize) -> Result<&T, TreeError> {
+        let current_node = unsafe { &*(self.current_link()?.as_ptr()) };
+        match current_node.childs.get(index) {
+            Some(child) => Ok(unsafe { &(*child.as_ptr()).elem }),
+            None => Err(TreeError::ChildOutOfRange {
+                index,
+                len: current_node.childs.len(),
+            }),
+        }
+    }
+
+    /// Same as [Tree::peek_child], but returns None instead of panicking.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push(1);
+    /// assert_eq!(tree.get_child(0), Some(&1));
+    /// assert_eq!(tree.get_child(1), None);
+    /// ```
+    pub fn get_child(&self, index: usize) -> Option<&T> {
+        self.try_peek_child(index).ok()
     }
 
     /// Same as [Tree::peek_child] but returns a mutable reference.
@@ -324,8 +632,26 @@ impl<T> Tree<T> {
             );
         }
 
-        unsafe { &mut (*(*self.current.unwrap().as_ptr()).childs[index].as_ptr()).elem }
+        unsafe { &mut (*(&(*self.current.unwrap().as_ptr()).childs)[index].as_ptr()).elem }
+    }
+
+    /// Same as [Tree::peek_child_mut], but returns an error instead of panicking.
+    pub fn try_peek_child_mut(&mut self, index: usize) -> Result<&mut T, TreeError> {
+        let current_node = unsafe { &*(self.current_link()?.as_ptr()) };
+        match current_node.childs.get(index) {
+            Some(child) => Ok(unsafe { &mut (*child.as_ptr()).elem }),
+            None => Err(TreeError::ChildOutOfRange {
+                index,
+                len: current_node.childs.len(),
+            }),
+        }
+    }
+
+    /// Same as [Tree::peek_child_mut], but returns None instead of panicking.
+    pub fn get_child_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.try_peek_child_mut(index).ok()
     }
+
     /// Returns 'current'.childs.len
     ///
     /// # Examples
@@ -342,6 +668,162 @@ impl<T> Tree<T> {
         unsafe { (*self.current.unwrap().as_ptr()).childs.len() }
     }
 
+    /// Same as [Tree::childs_len], but returns [TreeError::EmptyTree] instead of panicking.
+    pub fn try_childs_len(&self) -> Result<usize, TreeError> {
+        Ok(unsafe { (*self.current_link()?.as_ptr()).childs.len() })
+    }
+
+    /// Return the number of nodes in the subtree rooted at 'current' (including 'current').
+    /// Note that this method will not panic if called on an empty tree, and returns 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// tree.navigate_to(0);
+    /// tree.push(3);
+    /// assert_eq!(tree.subtree_len(), 2);
+    /// tree.go_to_root();
+    /// assert_eq!(tree.subtree_len(), 4);
+    /// ```
+    pub fn subtree_len(&self) -> usize {
+        _subtree_len(self.current)
+    }
+
+    /// Return the distance between 'root' and 'current' (0 at 'root').
+    /// Note that this method will not panic if called on an empty tree, and returns 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push(1);
+    /// tree.navigate_to(0);
+    /// tree.push(2);
+    /// tree.navigate_to(0);
+    /// assert_eq!(tree.depth(), 2);
+    /// ```
+    pub fn depth(&self) -> usize {
+        _depth(self.current)
+    }
+
+    /// Return the length of the longest path from 'current' to a leaf of its subtree (0 if
+    /// 'current' has no childs).
+    /// Note that this method will not panic if called on an empty tree, and returns 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// tree.navigate_to(1);
+    /// tree.push(3);
+    /// tree.go_to_root();
+    /// assert_eq!(tree.height(), 2);
+    /// ```
+    pub fn height(&self) -> usize {
+        _height(self.current)
+    }
+
+    /// Return the number of nodes without childs in the subtree rooted at 'current'.
+    /// Note that this method will not panic if called on an empty tree, and returns 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// tree.navigate_to(1);
+    /// tree.push(3);
+    /// tree.go_to_root();
+    /// assert_eq!(tree.leaf_count(), 2);
+    /// ```
+    pub fn leaf_count(&self) -> usize {
+        _leaf_count(self.current)
+    }
+
+    /// Return a [TreeStats] report on the shape of the subtree rooted at 'current', computed in a
+    /// single traversal.
+    /// Note that this method will not panic if called on an empty tree.
+    pub fn stats(&self) -> TreeStats {
+        TreeStats::new(self.current)
+    }
+
+    /// Return a [Render] drawing the subtree rooted at 'current', one node per line, like the Unix
+    /// `tree` command. See [Render] for the available options, and [Tree::render_root] to draw the
+    /// whole tree.
+    /// Note that this method will not panic if called on an empty tree, and draws nothing.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// tree.navigate_to(0);
+    /// tree.push(3);
+    /// assert_eq!(tree.render().to_string(), "1\n└── 3");
+    /// ```
+    pub fn render(&self) -> Render<'_, T> {
+        Render::new(self.current, self.current)
+    }
+
+    /// Return a [Render] drawing the whole tree from 'root', so that the position of 'current' in
+    /// the tree can be shown with [Render::highlight_current]. See [Tree::render].
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// tree.navigate_to(1);
+    /// assert_eq!(
+    ///     tree.render_root().highlight_current(true).to_string(),
+    ///     "0\n├── 1\n└── 2 <"
+    /// );
+    /// ```
+    pub fn render_root(&self) -> Render<'_, T> {
+        Render::new(self.root, self.current)
+    }
+
+    /// Write the whole tree (from 'root', as for [Tree::render_root]) as a Graphviz digraph to writer,
+    /// labelling the nodes with the [fmt::Display] of their elements and drawing the node where
+    /// 'current' sits in bold red. Cursors export only the subtree rooted at them.
+    /// Use [Tree::dot] or [Tree::dot_with] for more options.
+    /// Note that this method will not panic if called on an empty tree.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push(1);
+    /// let mut file = Vec::new();
+    /// tree.to_dot(&mut file).unwrap();
+    /// ```
+    pub fn to_dot<W: io::Write>(&self, writer: W) -> io::Result<()>
+    where
+        T: fmt::Display,
+    {
+        self.dot().write_to(writer)
+    }
+
+    /// Return a [Dot] exporter for the whole tree, labelling the nodes with the [fmt::Display] of
+    /// their elements. See [Dot] for the available options.
+    pub fn dot(&self) -> Dot<'_, T>
+    where
+        T: fmt::Display,
+    {
+        Dot::new(self.root, self.current, |elem: &T| elem.to_string())
+    }
+
+    /// Return a [Dot] exporter for the whole tree, labelling the nodes with label.
+    pub fn dot_with<'a, F>(&'a self, label: F) -> Dot<'a, T>
+    where
+        F: Fn(&T) -> String + 'a,
+    {
+        Dot::new(self.root, self.current, label)
+    }
+
     /// Return an iterator over the elements of current
     pub fn iter_childs(&self) -> ChildIterator<'_, T> {
         if self.is_empty() {
@@ -401,6 +883,36 @@ impl<T> Tree<T> {
         }
     }
 
+    /// Same as [Tree::join], but returns an error instead of panicking if either tree is empty or
+    /// if index > tree.childs_len(). The error comes with other, so that it is not lost.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::{Tree, TreeError};
+    /// let mut tree1 = Tree::from_element(0);
+    /// let tree2 = Tree::from_element(1);
+    /// let (err, tree2) = tree1.try_join(tree2, 1).unwrap_err();
+    /// assert_eq!(err, TreeError::ChildOutOfRange { index: 1, len: 0 });
+    /// assert_eq!(tree2.peek(), &1);
+    /// assert_eq!(tree1.try_join(tree2, 0), Ok(()));
+    /// let (err, _) = tree1.try_join(Tree::default(), 0).unwrap_err();
+    /// assert_eq!(err, TreeError::EmptyTree);
+    /// ```
+    pub fn try_join(&mut self, other: Tree<T>, index: usize) -> Result<(), (TreeError, Tree<T>)> {
+        let len = match self.try_childs_len() {
+            Ok(len) => len,
+            Err(err) => return Err((err, other)),
+        };
+        if other.is_empty() {
+            return Err((TreeError::EmptyTree, other));
+        }
+        if index > len {
+            return Err((TreeError::ChildOutOfRange { index, len }, other));
+        }
+        self.join(other, index);
+        Ok(())
+    }
+
     /// Remove from 'current' the subtree rooted in 'current'.childs\[index\] and return it as a new
     /// tree. This method also serves a remove method. It can also be used to dropped the subtree
     /// above the node you want to split at. It really does a lot of things...
@@ -449,13 +961,246 @@ impl<T> Tree<T> {
         }
     }
 
-    /// Return a [Cursor] pointing at 'current'
+    /// Same as [Tree::split], but returns an error instead of panicking.
     ///
     /// # Examples
     /// ```
-    /// # use libtree::Tree;
-    /// let tree = Tree::from_element(5);
-    /// let cursor = tree.cursor();
+    /// # use libtree::{Tree, TreeError};
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push(1);
+    /// assert!(tree.try_split(1).is_err());
+    /// assert_eq!(tree.try_split(0).unwrap().peek(), &1);
+    /// ```
+    pub fn try_split(&mut self, index: usize) -> Result<Tree<T>, TreeError> {
+        let len = self.try_childs_len()?;
+        if index >= len {
+            return Err(TreeError::ChildOutOfRange { index, len });
+        }
+        Ok(self.split(index))
+    }
+
+    /// Make 'current' the new 'root' of the tree, freeing every node which is not in the subtree
+    /// rooted at 'current'. Nothing is copied or moved, and only the freed nodes are visited, so
+    /// this is the way to keep the part of a game tree under the move just played.
+    /// Handles to the nodes kept stay valid.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// tree.navigate_to(1);
+    /// tree.push_iter(vec![3, 4]);
+    /// tree.navigate_to(0);
+    /// let handle = tree.handle();
+    /// tree.ascend();
+    /// tree.reroot_at_current();
+    /// assert_eq!(tree.has_father(), false);
+    /// tree.go_to_root();
+    /// assert_eq!(tree.iter().collect::<Vec<&i32>>(), vec![&2, &3, &4]);
+    /// tree.navigate_to_handle(&handle).unwrap();
+    /// assert_eq!(tree.peek(), &3);
+    /// ```
+    ///
+    /// # Panics
+    /// This method will panic if called on an empty tree.
+    pub fn reroot_at_current(&mut self) {
+        if self.is_empty() {
+            panic!("Tried to call reroot_at_current on an empty tree");
+        }
+
+        let current = self.current.unwrap();
+        if let Some(index) = _child_index(current) {
+            unsafe {
+                let father = (*current.as_ptr()).father.take().unwrap();
+                (*father.as_ptr()).childs.remove(index);
+            }
+            _free_subtree(self.root.unwrap());
+            self.root = Some(current);
+        }
+    }
+
+    /// Navigate 'current' to 'current'.childs\[index\] and make it the new 'root' of the tree, see
+    /// [Tree::reroot_at_current].
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// tree.navigate_to(1);
+    /// tree.push(3);
+    /// tree.go_to_root();
+    /// tree.promote_child(1);
+    /// assert_eq!(tree.peek(), &2);
+    /// assert_eq!(tree.into_vec(), vec![2, 3]);
+    /// ```
+    ///
+    /// # Panics
+    /// This method will panic if index >= tree.childs_len()
+    pub fn promote_child(&mut self, index: usize) {
+        if self.is_empty() {
+            panic!("Tried to call promote_child on an empty tree");
+        }
+        if index >= self.childs_len() {
+            panic!(
+                "Tried to call promote_child with index {} but current has only {} childs",
+                index,
+                self.childs_len()
+            );
+        }
+        self.navigate_to(index);
+        self.reroot_at_current();
+    }
+
+    /// Same as [Tree::promote_child], but returns an error instead of panicking.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::{Tree, TreeError};
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push(1);
+    /// assert_eq!(
+    ///     tree.try_promote_child(1),
+    ///     Err(TreeError::ChildOutOfRange { index: 1, len: 1 })
+    /// );
+    /// assert_eq!(tree.try_promote_child(0), Ok(()));
+    /// assert_eq!(tree.peek(), &1);
+    /// ```
+    pub fn try_promote_child(&mut self, index: usize) -> Result<(), TreeError> {
+        let len = self.try_childs_len()?;
+        if index >= len {
+            return Err(TreeError::ChildOutOfRange { index, len });
+        }
+        self.promote_child(index);
+        Ok(())
+    }
+
+    /// Remove the node 'current'.childs\[index\] from the tree and return its element. Its childs
+    /// are not removed: they take its place in 'current'.childs, in the same order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2, 3]);
+    /// tree.navigate_to(1);
+    /// tree.push_iter(vec![4, 5]);
+    /// tree.ascend();
+    /// assert_eq!(tree.remove_child_splice(1), 2);
+    /// assert_eq!(tree.iter_childs().collect::<Vec<&i32>>(), vec![&1, &4, &5, &3]);
+    /// tree.navigate_to(2);
+    /// tree.ascend();
+    /// assert_eq!(tree.peek(), &0);
+    /// ```
+    ///
+    /// # Panics
+    /// This method will panic if index >= tree.childs_len()
+    pub fn remove_child_splice(&mut self, index: usize) -> T {
+        if self.is_empty() {
+            panic!("Tried to call remove_child_splice on an empty tree");
+        }
+        if index >= self.childs_len() {
+            panic!(
+                "Tried to call remove_child_splice with index {} but current has only {} childs",
+                index,
+                self.childs_len()
+            );
+        }
+        _remove_child_splice(self.current.unwrap(), index)
+    }
+
+    /// Same as [Tree::remove_child_splice], but returns an error instead of panicking.
+    pub fn try_remove_child_splice(&mut self, index: usize) -> Result<T, TreeError> {
+        let len = self.try_childs_len()?;
+        if index >= len {
+            return Err(TreeError::ChildOutOfRange { index, len });
+        }
+        Ok(_remove_child_splice(self.current.unwrap(), index))
+    }
+
+    /// Remove from the subtree rooted at 'current' every subtree whose root does not satisfy f,
+    /// i.e. keep only the nodes for which f returns true (and whose ancestors were all kept).
+    /// 'current' itself is never tested nor removed, and f is never called on a node inside an
+    /// already removed subtree.
+    /// Note that this method will not panic if called on an empty tree.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2, 3]);
+    /// tree.navigate_to(1);
+    /// tree.push_iter(vec![4, 5]);
+    /// tree.go_to_root();
+    /// tree.retain(|el| el % 2 == 0);
+    /// assert_eq!(tree.iter().collect::<Vec<&i32>>(), vec![&0, &2, &4]);
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.prune_where(|el| !f(el));
+    }
+
+    /// Remove from the subtree rooted at 'current' every subtree whose root satisfies f. This is
+    /// the opposite of [Tree::retain].
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2, 3]);
+    /// tree.navigate_to(1);
+    /// tree.push_iter(vec![4, 5]);
+    /// tree.go_to_root();
+    /// tree.prune_where(|el| *el == 2);
+    /// assert_eq!(tree.iter().collect::<Vec<&i32>>(), vec![&0, &1, &3]);
+    /// ```
+    pub fn prune_where<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        if let Some(current) = self.current {
+            _drain_filter(current, f);
+        }
+    }
+
+    /// Same as [Tree::prune_where], but the removed subtrees are returned as new trees instead of
+    /// being dropped. The childs of a node are all tested (and removed) before its descendants.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2, 3]);
+    /// tree.navigate_to(1);
+    /// tree.push_iter(vec![4, 5]);
+    /// tree.go_to_root();
+    /// let removed = tree.drain_filter(|el| el % 2 == 1);
+    /// assert_eq!(tree.iter().collect::<Vec<&i32>>(), vec![&0, &2, &4]);
+    /// assert_eq!(
+    ///     removed.iter().map(|tree| *tree.peek()).collect::<Vec<i32>>(),
+    ///     vec![1, 3, 5]
+    /// );
+    /// ```
+    pub fn drain_filter<F>(&mut self, f: F) -> Vec<Tree<T>>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        match self.current {
+            Some(current) => _drain_filter(current, f),
+            None => Vec::new(),
+        }
+    }
+
+    /// Return a [Cursor] pointing at 'current'
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let tree = Tree::from_element(5);
+    /// let cursor = tree.cursor();
     /// assert_eq!(cursor.peek(), &5);
     /// ```
     pub fn cursor(&self) -> Cursor<'_, T> {
@@ -469,6 +1214,14 @@ impl<T> Tree<T> {
         }
     }
 
+    /// Same as [Tree::cursor], but returns [TreeError::EmptyTree] instead of panicking.
+    pub fn try_cursor(&self) -> Result<Cursor<'_, T>, TreeError> {
+        Ok(Cursor {
+            current: self.current.ok_or(TreeError::EmptyTree)?,
+            _boo: PhantomData,
+        })
+    }
+
     /// Return a [CursorMut] pointing at 'current'
     ///
     /// # Examples
@@ -485,10 +1238,20 @@ impl<T> Tree<T> {
 
         CursorMut {
             current: self.current.unwrap(),
+            tree_current: NonNull::from(&mut self.current),
             _boo: PhantomData,
         }
     }
 
+    /// Same as [Tree::cursor_mut], but returns [TreeError::EmptyTree] instead of panicking.
+    pub fn try_cursor_mut(&mut self) -> Result<CursorMut<'_, T>, TreeError> {
+        Ok(CursorMut {
+            current: self.current.ok_or(TreeError::EmptyTree)?,
+            tree_current: NonNull::from(&mut self.current),
+            _boo: PhantomData,
+        })
+    }
+
     /// Return an [UnsafeCursor] pointing at 'current'
     ///
     /// # Safety
@@ -513,6 +1276,14 @@ impl<T> Tree<T> {
         }
     }
 
+    /// Same as [Tree::unsafe_cursor], but returns [TreeError::EmptyTree] instead of panicking.
+    pub fn try_unsafe_cursor(&self) -> Result<UnsafeCursor<'_, T>, TreeError> {
+        Ok(UnsafeCursor {
+            current: self.current.ok_or(TreeError::EmptyTree)?,
+            _boo: PhantomData,
+        })
+    }
+
     /// Return a [Cursor] pointing at 'root'
     ///
     /// # Examples
@@ -535,6 +1306,14 @@ impl<T> Tree<T> {
         }
     }
 
+    /// Same as [Tree::cursor_root], but returns [TreeError::EmptyTree] instead of panicking.
+    pub fn try_cursor_root(&self) -> Result<Cursor<'_, T>, TreeError> {
+        Ok(Cursor {
+            current: self.root.ok_or(TreeError::EmptyTree)?,
+            _boo: PhantomData,
+        })
+    }
+
     /// Return a [CursorMut] pointing at 'root'
     ///
     /// # Examples
@@ -553,10 +1332,20 @@ impl<T> Tree<T> {
 
         CursorMut {
             current: self.root.unwrap(),
+            tree_current: NonNull::from(&mut self.current),
             _boo: PhantomData,
         }
     }
 
+    /// Same as [Tree::cursor_root_mut], but returns [TreeError::EmptyTree] instead of panicking.
+    pub fn try_cursor_root_mut(&mut self) -> Result<CursorMut<'_, T>, TreeError> {
+        Ok(CursorMut {
+            current: self.root.ok_or(TreeError::EmptyTree)?,
+            tree_current: NonNull::from(&mut self.current),
+            _boo: PhantomData,
+        })
+    }
+
     /// Return an [UnsafeCursor] pointing at 'root'
     ///
     /// # Safety
@@ -583,6 +1372,86 @@ impl<T> Tree<T> {
         }
     }
 
+    /// Same as [Tree::unsafe_cursor_root], but returns [TreeError::EmptyTree] instead of panicking.
+    pub fn try_unsafe_cursor_root(&self) -> Result<UnsafeCursor<'_, T>, TreeError> {
+        Ok(UnsafeCursor {
+            current: self.root.ok_or(TreeError::EmptyTree)?,
+            _boo: PhantomData,
+        })
+    }
+
+    /// Return a [NodeHandle] to 'current', that can be stored and used later to come back to
+    /// this node.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push(1);
+    /// tree.navigate_to(0);
+    /// let handle = tree.handle();
+    /// tree.go_to_root();
+    /// tree.navigate_to_handle(&handle).unwrap();
+    /// assert_eq!(tree.peek(), &1);
+    /// ```
+    pub fn handle(&self) -> NodeHandle<T> {
+        if self.is_empty() {
+            panic!("Tried to call handle on an empty tree");
+        }
+        NodeHandle::new(self.current.unwrap())
+    }
+
+    /// Return the node pointed by handle if it still belongs to this tree.
+    fn resolve_handle(&self, handle: &NodeHandle<T>) -> Result<ChildLink<T>, TreeError> {
+        let root = self.root.ok_or(TreeError::EmptyTree)?;
+        handle.resolve(root).ok_or(TreeError::StaleNode)
+    }
+
+    /// Return true if the node pointed by handle is still alive and belongs to this tree.
+    pub fn contains_handle(&self, handle: &NodeHandle<T>) -> bool {
+        self.resolve_handle(handle).is_ok()
+    }
+
+    /// Set 'current' to the node pointed by handle. Returns [TreeError::StaleNode] if the node
+    /// has been removed from this tree since the handle was created.
+    ///
+    /// This method needs to ascend from the node to 'root' to check that the node is still in
+    /// this tree, so it is linear in the depth of the node.
+    pub fn navigate_to_handle(&mut self, handle: &NodeHandle<T>) -> Result<(), TreeError> {
+        self.current = Some(self.resolve_handle(handle)?);
+        Ok(())
+    }
+
+    /// Return a [Cursor] pointing at the node pointed by handle, or [TreeError::StaleNode] if the
+    /// node has been removed from this tree.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push(1);
+    /// let mut cursor = tree.cursor();
+    /// cursor.navigate_to(0);
+    /// let handle = cursor.handle();
+    /// assert_eq!(tree.cursor_at(&handle).unwrap().peek(), &1);
+    /// ```
+    pub fn cursor_at(&self, handle: &NodeHandle<T>) -> Result<Cursor<'_, T>, TreeError> {
+        Ok(Cursor {
+            current: self.resolve_handle(handle)?,
+            _boo: PhantomData,
+        })
+    }
+
+    /// Return a [CursorMut] pointing at the node pointed by handle, or [TreeError::StaleNode] if
+    /// the node has been removed from this tree.
+    pub fn cursor_mut_at(&mut self, handle: &NodeHandle<T>) -> Result<CursorMut<'_, T>, TreeError> {
+        Ok(CursorMut {
+            current: self.resolve_handle(handle)?,
+            tree_current: NonNull::from(&mut self.current),
+            _boo: PhantomData,
+        })
+    }
+
     /// Collect the subtree in a depth-first order rooted at 'current' into a vec, and ascend 'current'.
     /// If 'current' is at 'root', and so it cannot ascend, the tree becomes an empty tree (and so
     /// most method will therefore fail).
@@ -590,6 +1459,7 @@ impl<T> Tree<T> {
     ///
     /// This behaviour is different from `Into<Vec>` implemented, where the whole tree is turned into
     /// a Vec, and not just a subtree.
+    /// To lazily consume the whole tree instead, use the [IntoIterator] implementation of [Tree].
     ///
     /// # Examples
     /// ```
@@ -622,12 +1492,9 @@ impl<T> Tree<T> {
 
         if !self.has_father() {
             // we are at root
-            let mut container = Vec::new();
-            _into_vec_rec(self.root.unwrap(), &mut container);
             // Clean pointer to avoid so that the tree drop won't cause double free
-            self.root = None;
             self.current = None;
-            container
+            IntoIter::preorder(self.root.take()).collect()
         } else {
             // we are not a root, so we ascend and we split the branch that is to be turned into a
             // vec
@@ -638,7 +1505,7 @@ impl<T> Tree<T> {
                 for (idx, child) in (*self.current.unwrap().as_ptr()).childs.iter().enumerate() {
                     if *child == old_current {
                         let mut old_tree = self.split(idx);
-                        _into_vec_rec(old_current, &mut container);
+                        container.extend(IntoIter::preorder(Some(old_current)));
                         // Clean pointer to avoid so that the tree drop won't cause double free
                         old_tree.root = None;
                         old_tree.current = None;
@@ -650,6 +1517,12 @@ impl<T> Tree<T> {
         }
     }
 
+    /// Same as [Tree::into_vec], but returns [TreeError::EmptyTree] instead of panicking.
+    pub fn try_into_vec(&mut self) -> Result<Vec<T>, TreeError> {
+        self.current_link()?;
+        Ok(self.into_vec())
+    }
+
     /// Iterate over references of element stored in the subtree rooted at 'current' in a
     /// depth-first way. This is done
     /// by creating a Vec and pushing every references into this Vec and then returning an iterator
@@ -677,7 +1550,7 @@ impl<T> Tree<T> {
             return Vec::new().into_iter();
         }
         let mut container = Vec::new();
-        _iter_rec(self.current.unwrap(), &mut container);
+        _iter_dfs(self.current.unwrap(), &mut container);
         container.into_iter()
     }
 
@@ -704,7 +1577,7 @@ impl<T> Tree<T> {
             return Vec::new().into_iter();
         }
         let mut container = Vec::new();
-        _iter_rec_mut(self.current.unwrap(), &mut container);
+        _iter_dfs_mut(self.current.unwrap(), &mut container);
         container.into_iter()
     }
 
@@ -759,87 +1632,460 @@ impl<T> Tree<T> {
             _boo: PhantomData,
         }
     }
-}
-
-pub struct ChildIterator<'a, T> {
-    pub(crate) current: ChildLink<T>,
-    pub(crate) i: usize,
-    pub(crate) _boo: PhantomData<&'a T>,
-    pub(crate) len: usize,
-}
 
-impl<'a, T> Iterator for ChildIterator<'a, T> {
-    type Item = &'a T;
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.i < self.len {
-            let item = unsafe { &(*(*self.current.as_ptr()).childs[self.i].as_ptr()).elem };
-            self.i += 1;
-            Some(item)
-        } else {
-            None
-        }
+    /// Iterate over the subtree rooted at 'current' in a lazy breadth-first way (level by level,
+    /// from left to right), returning references to the elements stored in the subtree.
+    /// Note that this method will not panic if called on an empty tree.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// tree.navigate_to(0);
+    /// tree.push_iter(vec![3, 4]);
+    /// tree.ascend();
+    /// assert_eq!(tree.iter_bfs().collect::<Vec<&i32>>(), vec![&0, &1, &2, &3, &4]);
+    /// tree.navigate_to(0);
+    /// assert_eq!(tree.iter_bfs().collect::<Vec<&i32>>(), vec![&1, &3, &4]);
+    /// ```
+    pub fn iter_bfs(&self) -> BfsIterator<'_, T> {
+        BfsIterator::new(self.current)
     }
-}
-
-pub struct ChildIteratorMut<'a, T> {
-    pub(crate) current: ChildLink<T>,
-    pub(crate) i: usize,
-    pub(crate) _boo: PhantomData<&'a T>,
-    pub(crate) len: usize,
-}
 
-impl<'a, T> Iterator for ChildIteratorMut<'a, T> {
-    type Item = &'a mut T;
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.i < self.len {
-            let item = unsafe { &mut (*(*self.current.as_ptr()).childs[self.i].as_ptr()).elem };
-            self.i += 1;
-            Some(item)
-        } else {
-            None
-        }
+    /// Same as [Tree::iter_bfs], but returns mutable references instead.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// for el in tree.iter_bfs_mut() {
+    ///     *el += 1;
+    /// }
+    /// assert_eq!(tree.iter_bfs().collect::<Vec<&i32>>(), vec![&1, &2, &3]);
+    /// ```
+    pub fn iter_bfs_mut(&mut self) -> BfsIteratorMut<'_, T> {
+        BfsIteratorMut::new(self.current)
     }
-}
-
-pub struct LazyTreeIterator<'a, T> {
-    pub(crate) cursor: Cursor<'a, T>,
-    pub(crate) idx_list: LinkedList<usize>,
-    pub(crate) _boo: PhantomData<&'a T>,
-}
+
+    /// Iterate over the levels of the subtree rooted at 'current', yielding a Vec with the
+    /// references of every node at the same depth ('current' being depth 0).
+    /// Note that this method will not panic if called on an empty tree.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// tree.navigate_to(1);
+    /// tree.push_iter(vec![3, 4]);
+    /// tree.go_to_root();
+    /// assert_eq!(
+    ///     tree.iter_levels().collect::<Vec<Vec<&i32>>>(),
+    ///     vec![vec![&0], vec![&1, &2], vec![&3, &4]]
+    /// );
+    /// ```
+    pub fn iter_levels(&self) -> LevelIterator<'_, T> {
+        LevelIterator::new(self.current)
+    }
+
+    /// Iterate over the subtree rooted at 'current' in a lazy post-order way, meaning that the
+    /// childs of a node are always returned before it (and so 'current' is returned last).
+    /// Unlike [Tree::iter], references are not collected into a Vec first: the iterator only keeps
+    /// the path from 'current' to the node being explored.
+    /// Note that this method will not panic if called on an empty tree.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// tree.navigate_to(0);
+    /// tree.push_iter(vec![3, 4]);
+    /// tree.ascend();
+    /// assert_eq!(tree.iter_postorder().collect::<Vec<&i32>>(), vec![&3, &4, &1, &2, &0]);
+    /// ```
+    pub fn iter_postorder(&self) -> PostOrderIterator<'_, T> {
+        PostOrderIterator::new(self.current)
+    }
+
+    /// Same as [Tree::iter_postorder], but returns mutable references instead.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// for el in tree.iter_postorder_mut() {
+    ///     *el += 1;
+    /// }
+    /// assert_eq!(tree.iter_postorder().collect::<Vec<&i32>>(), vec![&2, &3, &1]);
+    /// ```
+    pub fn iter_postorder_mut(&mut self) -> PostOrderIteratorMut<'_, T> {
+        PostOrderIteratorMut::new(self.current)
+    }
+
+    /// Consume the whole tree (and not only the subtree rooted at 'current') into an iterator
+    /// yielding owned elements in a depth-first pre-order way. This is the iterator used by the
+    /// [IntoIterator] implementation of [Tree].
+    ///
+    /// Nodes are freed as soon as their element is yielded. Note that this method will not panic
+    /// if called on an empty tree.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// tree.navigate_to(0);
+    /// tree.push(3);
+    /// assert_eq!(tree.into_iter_preorder().collect::<Vec<i32>>(), vec![0, 1, 3, 2]);
+    /// ```
+    pub fn into_iter_preorder(mut self) -> IntoIter<T> {
+        self.current = None;
+        IntoIter::preorder(self.root.take())
+    }
+
+    /// Same as [Tree::into_iter_preorder], but the elements are yielded in post-order (childs
+    /// before their father).
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// tree.navigate_to(0);
+    /// tree.push(3);
+    /// assert_eq!(tree.into_iter_postorder().collect::<Vec<i32>>(), vec![3, 1, 2, 0]);
+    /// ```
+    pub fn into_iter_postorder(mut self) -> IntoIter<T> {
+        self.current = None;
+        IntoIter::postorder(self.root.take())
+    }
+
+    /// Same as [Tree::into_iter_preorder], but the elements are yielded in a breadth-first way.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// tree.navigate_to(0);
+    /// tree.push(3);
+    /// assert_eq!(tree.into_iter_bfs().collect::<Vec<i32>>(), vec![0, 1, 2, 3]);
+    /// ```
+    pub fn into_iter_bfs(mut self) -> IntoIter<T> {
+        self.current = None;
+        IntoIter::bfs(self.root.take())
+    }
+
+    /// Consume the tree into a tree of the same shape, holding f(elem) in place of each elem.
+    /// 'current' stays at the same position. Elements are mapped in a depth-first pre-order way.
+    /// Note that this method will not panic if called on an empty tree.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(0);
+    /// tree.push_iter(vec![1, 2]);
+    /// tree.navigate_to(1);
+    /// let tree = tree.map(|elem| elem.to_string());
+    /// assert_eq!(tree.peek(), "2");
+    /// assert_eq!(tree.path(), vec![1]);
+    /// ```
+    pub fn map<U, F>(self, mut f: F) -> Tree<U>
+    where
+        F: FnMut(T) -> U,
+    {
+        match self.try_map(|elem| Ok::<U, Infallible>(f(elem))) {
+            Ok(tree) => tree,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Same as [Tree::map], but stops at the first error returned by f and returns it. In this
+    /// case, the tree is dropped.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element("1");
+    /// tree.push_iter(vec!["2", "x", "y"]);
+    /// assert!(tree.clone().try_map(|elem| elem.parse::<i32>()).is_err());
+    /// tree.navigate_to(1);
+    /// *tree.peek_mut() = "3";
+    /// tree.ascend();
+    /// tree.split(2);
+    /// let tree = tree.try_map(|elem| elem.parse::<i32>()).unwrap();
+    /// assert_eq!(tree.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    /// ```
+    pub fn try_map<U, E, F>(mut self, mut f: F) -> Result<Tree<U>, E>
+    where
+        F: FnMut(T) -> Result<U, E>,
+    {
+        let current = self.current.take();
+        let root = match self.root.take() {
+            Some(root) => root,
+            None => return Ok(Tree::default()),
+        };
+
+        let mut new_tree = Tree::default();
+        // nodes left to map, with the new father of their mapped node
+        let mut pending = Pending {
+            stack: vec![(root, None)],
+        };
+        while let Some((link, new_father)) = pending.stack.pop() {
+            let node = unsafe { Box::from_raw(link.as_ptr()) };
+            let Node { elem, childs, .. } = *node;
+            // the childs are pending before f is called, so they are freed if f fails or panics
+            let first_child = pending.stack.len();
+            pending
+                .stack
+                .extend(childs.into_iter().rev().map(|child| (child, None)));
+            let new_link = _attach(f(elem)?, new_father);
+
+            if new_father.is_none() {
+                new_tree.root = Some(new_link);
+                new_tree.current = Some(new_link);
+            }
+            if Some(link) == current {
+                new_tree.current = Some(new_link);
+            }
+            for (_, father) in &mut pending.stack[first_child..] {
+                *father = Some(new_link);
+            }
+        }
+        Ok(new_tree)
+    }
+
+    /// Fold the subtree rooted at 'current' from the leaves up, see [Cursor::fold].
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(1);
+    /// tree.push_iter(vec![2, 3]);
+    /// tree.navigate_to(0);
+    /// tree.push(4);
+    /// tree.go_to_root();
+    /// let sum = tree.fold(|elem, childs: Vec<i32>| elem + childs.iter().sum::<i32>());
+    /// assert_eq!(sum, 10);
+    /// ```
+    ///
+    /// # Panics
+    /// This method will panic if called on an empty tree. Use [Tree::try_cursor] to fold a
+    /// possibly empty tree.
+    pub fn fold<R, F>(&self, f: F) -> R
+    where
+        F: FnMut(&T, Vec<R>) -> R,
+    {
+        if self.is_empty() {
+            panic!("Tried to call fold on an empty tree");
+        }
+        _fold(self.current.unwrap(), f)
+    }
+
+    /// Fold the subtree rooted at 'current' from the leaves up into a tree, see
+    /// [Cursor::fold_to_tree].
+    ///
+    /// # Panics
+    /// This method will panic if called on an empty tree.
+    pub fn fold_to_tree<R, F>(&self, f: F) -> Tree<R>
+    where
+        F: FnMut(&T, Vec<&R>) -> R,
+    {
+        if self.is_empty() {
+            panic!("Tried to call fold_to_tree on an empty tree");
+        }
+        _fold_to_tree(self.current.unwrap(), f)
+    }
+
+    /// Return a tree of the same shape, holding f(&elem) in place of each elem. 'current' is at
+    /// the same position in both trees. Elements are mapped in a depth-first pre-order way.
+    /// Note that this method will not panic if called on an empty tree.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let mut tree = Tree::from_element(String::from("a"));
+    /// tree.push_iter(vec![String::from("bc"), String::from("def")]);
+    /// let lens = tree.map_ref(|elem| elem.len());
+    /// assert_eq!(lens.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    /// assert_eq!(tree.peek(), "a");
+    /// ```
+    pub fn map_ref<U, F>(&self, mut f: F) -> Tree<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        let mut new_tree = Tree::default();
+        let mut stack: Vec<(ChildLink<T>, Link<U>)> =
+            self.root.map(|root| (root, None)).into_iter().collect();
+        while let Some((link, new_father)) = stack.pop() {
+            let node = unsafe { &*link.as_ptr() };
+            let new_link = _attach(f(&node.elem), new_father);
+            if new_father.is_none() {
+                new_tree.root = Some(new_link);
+            }
+            if Some(link) == self.current {
+                new_tree.current = Some(new_link);
+            }
+            stack.extend(
+                node.childs
+                    .iter()
+                    .rev()
+                    .map(|child| (*child, Some(new_link))),
+            );
+        }
+        new_tree
+    }
+
+    /// Return a tree of the same shape, where each node holds the value accumulated along the
+    /// path from 'root' to it: the value of a node is f(value of its father, elem), the father of
+    /// 'root' having init as value. 'current' is at the same position in both trees.
+    /// Note that this method will not panic if called on an empty tree.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// // probability of reaching each node, each node holding the probability of its edge
+    /// let tree: Tree<f64> = "(1 (0.5 0.2 0.8) 0.5)".parse().unwrap();
+    /// let reach = tree.scan(1.0, |father, p| father * p);
+    /// assert_eq!(
+    ///     reach.iter().collect::<Vec<_>>(),
+    ///     vec![&1.0, &0.5, &0.1, &0.4, &0.5]
+    /// );
+    /// ```
+    pub fn scan<Acc, F>(&self, init: Acc, mut f: F) -> Tree<Acc>
+    where
+        F: FnMut(&Acc, &T) -> Acc,
+    {
+        let mut new_tree = Tree::default();
+        let mut stack: Vec<(ChildLink<T>, Link<Acc>)> =
+            self.root.map(|root| (root, None)).into_iter().collect();
+        while let Some((link, new_father)) = stack.pop() {
+            let node = unsafe { &*link.as_ptr() };
+            let acc = match new_father {
+                Some(new_father) => f(unsafe { &(*new_father.as_ptr()).elem }, &node.elem),
+                None => f(&init, &node.elem),
+            };
+            let new_link = _attach(acc, new_father);
+            if new_father.is_none() {
+                new_tree.root = Some(new_link);
+            }
+            if Some(link) == self.current {
+                new_tree.current = Some(new_link);
+            }
+            stack.extend(
+                node.childs
+                    .iter()
+                    .rev()
+                    .map(|child| (*child, Some(new_link))),
+            );
+        }
+        new_tree
+    }
+
+    /// Iterate lazily over the subtree rooted at 'current' in the same depth-first pre-order way
+    /// as [Tree::iter], yielding each element with the value accumulated along the path from
+    /// 'current' to it (see [Tree::scan], 'current' playing the role of 'root').
+    /// Note that this method will not panic if called on an empty tree.
+    ///
+    /// # Examples
+    /// ```
+    /// # use libtree::Tree;
+    /// let tree: Tree<i32> = "(1 (2 3) 4)".parse().unwrap();
+    /// let depths: Vec<(i32, usize)> = tree
+    ///     .scan_iter(0, |depth, _| depth + 1)
+    ///     .map(|(elem, depth)| (*elem, depth))
+    ///     .collect();
+    /// assert_eq!(depths, vec![(1, 1), (2, 2), (3, 3), (4, 2)]);
+    /// ```
+    pub fn scan_iter<Acc, F>(&self, init: Acc, f: F) -> ScanIterator<'_, T, Acc, F>
+    where
+        Acc: Clone,
+        F: FnMut(&Acc, &T) -> Acc,
+    {
+        ScanIterator::new(self.current, init, f)
+    }
+}
+
+pub struct ChildIterator<'a, T> {
+    pub(crate) current: ChildLink<T>,
+    pub(crate) i: usize,
+    pub(crate) _boo: PhantomData<&'a T>,
+    pub(crate) len: usize,
+}
+
+impl<'a, T> Iterator for ChildIterator<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.i < self.len {
+            let item = unsafe { &(*(&(*self.current.as_ptr()).childs)[self.i].as_ptr()).elem };
+            self.i += 1;
+            Some(item)
+        } else {
+            None
+        }
+    }
+}
+
+pub struct ChildIteratorMut<'a, T> {
+    pub(crate) current: ChildLink<T>,
+    pub(crate) i: usize,
+    pub(crate) _boo: PhantomData<&'a T>,
+    pub(crate) len: usize,
+}
+
+impl<'a, T> Iterator for ChildIteratorMut<'a, T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.i < self.len {
+            let item = unsafe { &mut (*(&(*self.current.as_ptr()).childs)[self.i].as_ptr()).elem };
+            self.i += 1;
+            Some(item)
+        } else {
+            None
+        }
+    }
+}
+
+pub struct LazyTreeIterator<'a, T> {
+    pub(crate) cursor: Cursor<'a, T>,
+    pub(crate) idx_list: LinkedList<usize>,
+    pub(crate) _boo: PhantomData<&'a T>,
+}
 
 impl<'a, T> Iterator for LazyTreeIterator<'a, T> {
     type Item = &'a T;
     fn next(&mut self) -> Option<Self::Item> {
-        if self.idx_list.is_empty() {
-            return None;
-        }
-
-        let res;
-        if self.cursor.childs_len() == 0 {
-            res = Some(self.cursor.peek());
-            self.cursor.ascend();
-            self.idx_list.pop_back();
-        } else if *self.idx_list.back().unwrap() < self.cursor.childs_len() {
-            if *self.idx_list.back().unwrap() == 0 {
-                res = Some(self.cursor.peek());
-                self.cursor.navigate_to(0);
+        // Loop instead of recursing, so that climbing back from a very deep leaf cannot overflow
+        // the stack.
+        loop {
+            let next_child = *self.idx_list.back()?;
+            if self.cursor.childs_len() == 0 {
+                let res = self.cursor.peek();
+                self.idx_list.pop_back();
+                if self.cursor.has_father() {
+                    self.cursor.ascend();
+                }
+                return Some(res);
+            } else if next_child < self.cursor.childs_len() {
                 *self.idx_list.back_mut().unwrap() += 1;
                 self.idx_list.push_back(0);
+                if next_child == 0 {
+                    let res = self.cursor.peek();
+                    self.cursor.navigate_to(0);
+                    return Some(res);
+                }
+                self.cursor.navigate_to(next_child);
             } else {
-                self.cursor.navigate_to(*self.idx_list.back().unwrap());
-                *self.idx_list.back_mut().unwrap() += 1;
-                self.idx_list.push_back(0);
-                res = self.next();
-            }
-        } else {
-            self.idx_list.pop_back();
-            if self.cursor.has_father() {
-                self.cursor.ascend();
+                self.idx_list.pop_back();
+                if self.cursor.has_father() {
+                    self.cursor.ascend();
+                }
             }
-            res = self.next();
         }
-
-        res
     }
 }
 
@@ -852,104 +2098,275 @@ pub struct LazyTreeIteratorMut<'a, T> {
 impl<'a, T> Iterator for LazyTreeIteratorMut<'a, T> {
     type Item = &'a mut T;
     fn next(&mut self) -> Option<Self::Item> {
-        if self.idx_list.is_empty() {
-            return None;
-        }
-
-        let res;
-        if self.cursor.childs_len() == 0 {
-            unsafe {
-                res = Some(self.cursor.peek_mut());
-            }
-            self.cursor.ascend();
-            self.idx_list.pop_back();
-        } else if *self.idx_list.back().unwrap() < self.cursor.childs_len() {
-            if *self.idx_list.back().unwrap() == 0 {
-                unsafe {
-                    res = Some(self.cursor.peek_mut());
+        // Same exploration as LazyTreeIterator
+        loop {
+            let next_child = *self.idx_list.back()?;
+            if self.cursor.childs_len() == 0 {
+                let res = unsafe { self.cursor.peek_mut() };
+                self.idx_list.pop_back();
+                if self.cursor.has_father() {
+                    self.cursor.ascend();
                 }
-                self.cursor.navigate_to(0);
+                return Some(res);
+            } else if next_child < self.cursor.childs_len() {
                 *self.idx_list.back_mut().unwrap() += 1;
                 self.idx_list.push_back(0);
+                if next_child == 0 {
+                    let res = unsafe { self.cursor.peek_mut() };
+                    self.cursor.navigate_to(0);
+                    return Some(res);
+                }
+                self.cursor.navigate_to(next_child);
             } else {
-                self.cursor.navigate_to(*self.idx_list.back().unwrap());
-                *self.idx_list.back_mut().unwrap() += 1;
-                self.idx_list.push_back(0);
-                res = self.next();
-            }
-        } else {
-            self.idx_list.pop_back();
-            if self.cursor.has_father() {
-                self.cursor.ascend();
+                self.idx_list.pop_back();
+                if self.cursor.has_father() {
+                    self.cursor.ascend();
+                }
             }
-            res = self.next();
         }
+    }
+}
+/// Gather the references of the subtree rooted at link into container, in depth-first order.
+pub fn _iter_dfs<'a, T>(link: ChildLink<T>, container: &mut Vec<&'a T>) {
+    let mut stack = vec![link];
+    while let Some(link) = stack.pop() {
+        unsafe {
+            let node: &'a Node<T> = &*link.as_ptr();
+            container.push(&node.elem);
+            stack.extend(node.childs.iter().rev());
+        }
+    }
+}
 
-        res
+/// Gather the mutable references of the subtree rooted at link into container, in depth-first
+/// order.
+pub fn _iter_dfs_mut<'a, T>(link: ChildLink<T>, container: &mut Vec<&'a mut T>) {
+    let mut stack = vec![link];
+    while let Some(link) = stack.pop() {
+        unsafe {
+            let node: &'a mut Node<T> = &mut *link.as_ptr();
+            stack.extend(node.childs.iter().rev());
+            container.push(&mut node.elem);
+        }
     }
 }
-/// Recursive function to gather reference of the subtree into container.
-pub fn _iter_rec<T>(link: ChildLink<T>, container: &mut Vec<&T>) {
-    unsafe {
-        container.push(&(*link.as_ptr()).elem);
-        for child in (*link.as_ptr()).childs.iter() {
-            _iter_rec(*child, container);
+
+/// Free every node of the subtree rooted at link.
+pub(crate) fn _free_subtree<T>(link: ChildLink<T>) {
+    let mut stack = vec![link];
+    while let Some(link) = stack.pop() {
+        let node = unsafe { Box::from_raw(link.as_ptr()) };
+        stack.extend(node.childs.iter());
+    }
+}
+
+/// Subtrees not mapped yet by [Tree::try_map], with the new father of their mapped root. They are
+/// freed on drop, so that nothing leaks if the mapping stops early or panics.
+struct Pending<T, U> {
+    stack: Vec<(ChildLink<T>, Link<U>)>,
+}
+
+impl<T, U> Drop for Pending<T, U> {
+    fn drop(&mut self) {
+        self.stack
+            .drain(..)
+            .for_each(|(link, _)| _free_subtree(link));
+    }
+}
+
+/// Roots of the subtrees already built by [_fold_to_tree], not linked to their father yet. They
+/// are freed on drop, so that nothing leaks if the fold panics.
+struct Folded<R> {
+    links: Vec<ChildLink<R>>,
+}
+
+impl<R> Drop for Folded<R> {
+    fn drop(&mut self) {
+        self.links.drain(..).for_each(_free_subtree);
+    }
+}
+
+/// Create a node holding elem as the last child of father.
+pub(crate) fn _attach<T>(elem: T, father: Link<T>) -> ChildLink<T> {
+    let link = unsafe {
+        NonNull::new_unchecked(Box::into_raw(Box::new(Node {
+            elem,
+            childs: Vec::new(),
+            token: OnceCell::new(),
+            father,
+        })))
+    };
+    if let Some(father) = father {
+        unsafe { (*father.as_ptr()).childs.push(link) };
+    }
+    link
+}
+
+/// Fold the subtree rooted at link from the leaves up, see [Cursor::fold].
+pub(crate) fn _fold<T, R, F>(link: ChildLink<T>, mut f: F) -> R
+where
+    F: FnMut(&T, Vec<R>) -> R,
+{
+    // results of the childs already folded, in order
+    let mut results: Vec<R> = Vec::new();
+    let mut stack = vec![(link, 0)];
+    while let Some((link, next)) = stack.last_mut() {
+        let node = unsafe { &*link.as_ptr() };
+        if *next < node.childs.len() {
+            let child = node.childs[*next];
+            *next += 1;
+            stack.push((child, 0));
+        } else {
+            let childs = results.split_off(results.len() - node.childs.len());
+            results.push(f(&node.elem, childs));
+            stack.pop();
         }
     }
+    results.pop().unwrap()
 }
 
-/// Recursive function to gather mutable reference of the subtree into container.
-pub fn _iter_rec_mut<T>(link: ChildLink<T>, container: &mut Vec<&mut T>) {
-    unsafe {
-        container.push(&mut (*link.as_ptr()).elem);
-        for child in (*link.as_ptr()).childs.iter() {
-            _iter_rec_mut(*child, container);
+/// Fold the subtree rooted at link from the leaves up into a tree, see [Cursor::fold_to_tree].
+pub(crate) fn _fold_to_tree<T, R, F>(link: ChildLink<T>, mut f: F) -> Tree<R>
+where
+    F: FnMut(&T, Vec<&R>) -> R,
+{
+    // nodes of the childs already folded, in order, not linked to their father yet
+    let mut folded = Folded { links: Vec::new() };
+    let mut stack = vec![(link, 0)];
+    while let Some((link, next)) = stack.last_mut() {
+        let node = unsafe { &*link.as_ptr() };
+        if *next < node.childs.len() {
+            let child = node.childs[*next];
+            *next += 1;
+            stack.push((child, 0));
+        } else {
+            // the childs stay in folded while f runs, so that they are freed if it panics
+            let start = folded.links.len() - node.childs.len();
+            let elem = f(
+                &node.elem,
+                folded.links[start..]
+                    .iter()
+                    .map(|child| unsafe { &(*child.as_ptr()).elem })
+                    .collect(),
+            );
+            let childs = folded.links.split_off(start);
+            let new_link = _attach(elem, None);
+            unsafe {
+                for child in childs.iter() {
+                    (*child.as_ptr()).father = Some(new_link);
+                }
+                (*new_link.as_ptr()).childs = childs;
+            }
+            folded.links.push(new_link);
+            stack.pop();
         }
     }
+    let root = folded.links.pop();
+    Tree {
+        root,
+        current: root,
+        _boo: PhantomData,
+    }
 }
 
-/// Reursive function to turn a subtree into a vec.
-fn _into_vec_rec<T>(link_node: ChildLink<T>, container: &mut Vec<T>) {
+/// Return the position of link in the childs of its father.
+pub(crate) fn _child_index<T>(link: ChildLink<T>) -> Option<usize> {
     unsafe {
-        let boxed_node = Box::from_raw(link_node.as_ptr());
-        let (el, childs) = (boxed_node.elem, boxed_node.childs);
-        container.push(el);
+        let father = (*link.as_ptr()).father?;
+        (*father.as_ptr())
+            .childs
+            .iter()
+            .position(|child| *child == link)
+    }
+}
+
+/// Return the child indices leading from the root of link to link.
+pub(crate) fn _path<T>(mut link: ChildLink<T>) -> Vec<usize> {
+    let mut path = Vec::new();
+    while let Some(index) = _child_index(link) {
+        path.push(index);
+        link = unsafe { (*link.as_ptr()).father.unwrap() };
+    }
+    path.reverse();
+    path
+}
+
+/// Return the node reached by following path from link.
+pub(crate) fn _follow_path<T>(
+    mut link: ChildLink<T>,
+    path: &[usize],
+) -> Result<ChildLink<T>, TreeError> {
+    for &index in path {
+        let childs = unsafe { &(*link.as_ptr()).childs };
+        link = *childs.get(index).ok_or(TreeError::ChildOutOfRange {
+            index,
+            len: childs.len(),
+        })?;
+    }
+    Ok(link)
+}
+
+/// Return the n-th ancestor of link.
+pub(crate) fn _ascend_n<T>(mut link: ChildLink<T>, n: usize) -> Result<ChildLink<T>, TreeError> {
+    for _ in 0..n {
+        link = unsafe { (*link.as_ptr()).father.ok_or(TreeError::NoFather)? };
+    }
+    Ok(link)
+}
 
-        for child in childs {
-            _into_vec_rec(child, container);
+/// Remove link.childs\[index\] and put its childs in its place, returning its element.
+pub(crate) fn _remove_child_splice<T>(link: ChildLink<T>, index: usize) -> T {
+    unsafe {
+        let removed = (*link.as_ptr()).childs.remove(index);
+        let removed = *Box::from_raw(removed.as_ptr());
+        for child in removed.childs.iter() {
+            (*child.as_ptr()).father = Some(link);
         }
+        (*link.as_ptr()).childs.splice(index..index, removed.childs);
+        removed.elem
     }
 }
 
-/// Recursive function to clone the tree under cursor.
-fn _clone_rec<T>(
-    cursor: &mut Cursor<'_, T>,
-    new_tree: &mut Tree<T>,
-    tree: &Tree<T>,
-) -> Option<NonNull<Node<T>>>
+/// Detach from the subtree rooted at link (link excluded) every subtree whose root satisfies f,
+/// and return them as new trees.
+pub(crate) fn _drain_filter<T, F>(link: ChildLink<T>, mut f: F) -> Vec<Tree<T>>
 where
-    T: Clone,
+    F: FnMut(&T) -> bool,
 {
-    let mut res = None;
-    if cursor.current == tree.current.unwrap() {
-        res = new_tree.current;
-    }
-
-    if cursor.childs_len() > 0 {
-        for i in 0..cursor.childs_len() {
-            new_tree.push(cursor.peek_child(i).clone());
-            new_tree.navigate_to(i);
-            cursor.navigate_to(i);
-            let ret = _clone_rec(cursor, new_tree, tree);
-            if ret.is_some() {
-                res = ret;
-            }
-            new_tree.ascend();
-            cursor.ascend();
+    let mut removed = Vec::new();
+    let mut stack = vec![link];
+    while let Some(link) = stack.pop() {
+        unsafe {
+            let node = &mut *link.as_ptr();
+            // retain keeps the childs not tested yet if f panics, so that nothing leaks
+            node.childs.retain(|child| {
+                if !f(&(*child.as_ptr()).elem) {
+                    return true;
+                }
+                (*child.as_ptr()).father = None;
+                removed.push(Tree {
+                    root: Some(*child),
+                    current: Some(*child),
+                    _boo: PhantomData,
+                });
+                false
+            });
+            stack.extend(node.childs.iter().rev());
         }
     }
+    removed
+}
 
-    res
+/// Iterate in pre-order over the elements of the subtree rooted at start, together with their
+/// number of childs. This sequence fully determines the shape of the subtree.
+fn _shape<'a, T: 'a>(start: Link<T>) -> impl Iterator<Item = (&'a T, usize)> {
+    let mut stack: Vec<ChildLink<T>> = start.into_iter().collect();
+    std::iter::from_fn(move || {
+        let link = stack.pop()?;
+        let node = unsafe { &*link.as_ptr() };
+        stack.extend(node.childs.iter().rev());
+        Some((&node.elem, node.childs.len()))
+    })
 }
 
 impl<T> Default for Tree<T> {
@@ -971,32 +2388,160 @@ where
             panic!("Tried to call clone on an empty tree");
         }
 
-        let mut cursor = self.cursor_root();
-        let mut new_tree = Self::from_element(cursor.peek().clone());
-        let new_current = _clone_rec(&mut cursor, &mut new_tree, self);
-        new_tree.current = new_current;
+        let root = self.root.unwrap();
+        let mut new_tree = Self::from_element(unsafe { (*root.as_ptr()).elem.clone() });
+        let mut stack = vec![(root, new_tree.root.unwrap())];
+        while let Some((link, new_link)) = stack.pop() {
+            if Some(link) == self.current {
+                new_tree.current = Some(new_link);
+            }
+            unsafe {
+                for child in (*link.as_ptr()).childs.iter() {
+                    let new_child = NonNull::new_unchecked(Box::into_raw(Box::new(Node {
+                        elem: (*child.as_ptr()).elem.clone(),
+                        childs: Vec::new(),
+                        token: OnceCell::new(),
+                        father: Some(new_link),
+                    })));
+                    (*new_link.as_ptr()).childs.push(new_child);
+                    stack.push((*child, new_child));
+                }
+            }
+        }
         new_tree
     }
 }
 
-impl<T> Into<Vec<T>> for Tree<T> {
-    fn into(mut self) -> Vec<T> {
-        self.go_to_root();
-        self.into_vec()
+impl<T> fmt::Debug for Tree<T>
+where
+    T: fmt::Debug,
+{
+    /// Write the tree from 'root' as `Tree(elem [child, child [...]])`, leaves being written
+    /// without brackets. With `{:#?}`, each node is written on its own line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pretty = f.alternate();
+        let root = match self.root {
+            Some(root) => root,
+            None => return write!(f, "Tree()"),
+        };
+
+        // Write the elem of link, opening its childs list if it has any. Returns true if the list
+        // was opened.
+        let open = |f: &mut fmt::Formatter<'_>, link: ChildLink<T>, depth: usize| {
+            let node = unsafe { &*link.as_ptr() };
+            if pretty {
+                let indent = "    ".repeat(depth);
+                let elem = format!("{:#?}", node.elem).replace('\n', &format!("\n{}", indent));
+                write!(f, "{}{}", indent, elem)?;
+                if node.childs.is_empty() {
+                    writeln!(f, ",")?;
+                } else {
+                    writeln!(f, " [")?;
+                }
+            } else {
+                write!(f, "{:?}", node.elem)?;
+                if !node.childs.is_empty() {
+                    write!(f, " [")?;
+                }
+            }
+            Ok::<bool, fmt::Error>(!node.childs.is_empty())
+        };
+
+        write!(f, "Tree(")?;
+        if pretty {
+            writeln!(f)?;
+        }
+        let mut stack = Vec::new();
+        if open(f, root, 1)? {
+            stack.push((root, 0));
+        }
+        while let Some((link, next)) = stack.last_mut() {
+            let childs = unsafe { &(*link.as_ptr()).childs };
+            if *next < childs.len() {
+                let child = childs[*next];
+                if *next > 0 && !pretty {
+                    write!(f, ", ")?;
+                }
+                *next += 1;
+                let depth = stack.len() + 1;
+                if open(f, child, depth)? {
+                    stack.push((child, 0));
+                }
+            } else {
+                stack.pop();
+                if pretty {
+                    writeln!(f, "{}],", "    ".repeat(stack.len() + 1))?;
+                } else {
+                    write!(f, "]")?;
+                }
+            }
+        }
+        write!(f, ")")
+    }
+}
+
+impl<T> PartialEq for Tree<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        _shape(self.root).eq(_shape(other.root))
+    }
+}
+
+impl<T> Eq for Tree<T> where T: Eq {}
+
+impl<T> Hash for Tree<T>
+where
+    T: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for (elem, childs_len) in _shape(self.root) {
+            elem.hash(state);
+            childs_len.hash(state);
+        }
+    }
+}
+
+impl<T> PartialOrd for Tree<T>
+where
+    T: PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        _shape(self.root).partial_cmp(_shape(other.root))
+    }
+}
+
+impl<T> Ord for Tree<T>
+where
+    T: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        _shape(self.root).cmp(_shape(other.root))
+    }
+}
+
+impl<T> IntoIterator for Tree<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    /// See [Tree::into_iter_preorder].
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_iter_preorder()
+    }
+}
+
+impl<T> From<Tree<T>> for Vec<T> {
+    fn from(mut tree: Tree<T>) -> Vec<T> {
+        tree.go_to_root();
+        tree.into_vec()
     }
 }
 
 impl<T> Drop for Tree<T> {
     fn drop(&mut self) {
-        if self.root.is_some() {
-            self.go_to_root();
-
-            for _ in 0..self.childs_len() {
-                self.split(0);
-            }
-            unsafe {
-                let _ = Box::from_raw(self.current.unwrap().as_ptr());
-            }
+        if let Some(root) = self.root.take() {
+            _free_subtree(root);
         }
     }
 }
@@ -1128,6 +2673,310 @@ mod test {
         tree.navigate_to(1);
     }
 
+    #[test]
+    fn try_methods_on_empty_tree() {
+        let mut tree: Tree<i32> = Tree::default();
+        assert_eq!(tree.try_push(1), Err(TreeError::EmptyTree));
+        assert_eq!(tree.try_navigate_to(0), Err(TreeError::EmptyTree));
+        assert_eq!(tree.try_ascend(), Err(TreeError::EmptyTree));
+        assert_eq!(tree.try_go_to_root(), Err(TreeError::EmptyTree));
+        assert_eq!(tree.try_split(0).err(), Some(TreeError::EmptyTree));
+        assert_eq!(tree.try_into_vec(), Err(TreeError::EmptyTree));
+        assert!(tree.try_cursor().is_err());
+        assert_eq!(tree.peek_opt(), None);
+        assert_eq!(tree.get_child(0), None);
+    }
+
+    #[test]
+    fn try_navigate_and_split() {
+        let mut tree = Tree::from_element(0);
+        tree.push_iter(vec![1, 2]);
+        assert_eq!(
+            tree.try_navigate_to(2),
+            Err(TreeError::ChildOutOfRange { index: 2, len: 2 })
+        );
+        assert_eq!(tree.peek(), &0);
+        let split_tree = tree.try_split(1).unwrap();
+        assert_eq!(split_tree.peek(), &2);
+        assert_eq!(
+            tree.try_split(1).err(),
+            Some(TreeError::ChildOutOfRange { index: 1, len: 1 })
+        );
+        tree.try_navigate_to(0).unwrap();
+        assert_eq!(tree.peek(), &1);
+        tree.try_ascend().unwrap();
+        assert_eq!(tree.try_ascend(), Err(TreeError::NoFather));
+    }
+
+    #[test]
+    fn handles() {
+        let mut tree = Tree::from_element(0);
+        tree.push_iter(vec![1, 2]);
+        tree.navigate_to(0);
+        tree.push(3);
+        let handle1 = tree.handle();
+        tree.navigate_to(0);
+        let handle3 = tree.handle();
+        tree.go_to_root();
+        let handle0 = tree.cursor().handle();
+        assert_eq!(handle0, tree.handle());
+        assert_ne!(handle0, handle1);
+
+        tree.navigate_to_handle(&handle3).unwrap();
+        assert_eq!(tree.peek(), &3);
+        assert_eq!(tree.cursor_at(&handle1).unwrap().peek(), &1);
+
+        // handle into a split subtree is rejected by the original tree
+        tree.go_to_root();
+        let mut split_tree = tree.split(0);
+        assert_eq!(tree.navigate_to_handle(&handle1), Err(TreeError::StaleNode));
+        assert!(handle3.is_alive());
+        assert!(split_tree.contains_handle(&handle3));
+        split_tree.navigate_to_handle(&handle3).unwrap();
+        assert_eq!(split_tree.peek(), &3);
+
+        // and detected once it has been freed
+        split_tree.go_to_root();
+        split_tree.into_vec();
+        assert!(!handle1.is_alive());
+        assert!(!handle3.is_alive());
+        assert_eq!(tree.navigate_to_handle(&handle3), Err(TreeError::StaleNode));
+        assert!(tree.contains_handle(&handle0));
+    }
+
+    #[test]
+    fn retain_and_drain_filter() {
+        let mut tree = Tree::from_element(0);
+        tree.push_iter(vec![1, 2, 3]);
+        tree.navigate_to(1);
+        tree.push_iter(vec![4, 5, 6]);
+        tree.navigate_to(1);
+        tree.push(7);
+        tree.go_to_root();
+
+        let mut clone = tree.clone();
+        let mut tested = Vec::new();
+        clone.retain(|el| {
+            tested.push(*el);
+            *el != 5 && *el != 3
+        });
+        tested.sort();
+        assert_eq!(tested, vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(
+            clone.iter().collect::<Vec<&i32>>(),
+            vec![&0, &1, &2, &4, &6]
+        );
+
+        tree.navigate_to(1);
+        let removed = tree.drain_filter(|el| el % 2 == 1);
+        assert_eq!(removed.len(), 1);
+        assert!(!removed[0].has_father());
+        assert_eq!(removed[0].iter().collect::<Vec<&i32>>(), vec![&5, &7]);
+        tree.go_to_root();
+        assert_eq!(
+            tree.iter().collect::<Vec<&i32>>(),
+            vec![&0, &1, &2, &4, &6, &3]
+        );
+    }
+
+    #[test]
+    fn remove_child_splice() {
+        let mut tree = Tree::from_element(0);
+        tree.push_iter(vec![1, 2]);
+        tree.navigate_to(0);
+        tree.push_iter(vec![3, 4]);
+        tree.navigate_to(1);
+        tree.push(5);
+        tree.go_to_root();
+        assert_eq!(tree.remove_child_splice(0), 1);
+        assert_eq!(tree.iter().collect::<Vec<&i32>>(), vec![&0, &3, &4, &5, &2]);
+        tree.navigate_to(1);
+        assert_eq!(tree.remove_child_splice(0), 5);
+        assert_eq!(tree.childs_len(), 0);
+        tree.ascend();
+        assert_eq!(tree.peek(), &0);
+        assert_eq!(
+            tree.try_remove_child_splice(3),
+            Err(TreeError::ChildOutOfRange { index: 3, len: 3 })
+        );
+    }
+
+    #[test]
+    fn path() {
+        let mut tree = Tree::from_element(0);
+        tree.push_iter(vec![1, 2, 3]);
+        tree.navigate_to(2);
+        tree.push_iter(vec![4, 5]);
+        tree.navigate_to(1);
+        tree.push(6);
+        tree.navigate_to(0);
+        assert_eq!(tree.path(), vec![2, 1, 0]);
+        assert_eq!(tree.child_index(), Some(0));
+        tree.ascend_n(2);
+        assert_eq!(tree.peek(), &3);
+        assert_eq!(tree.try_ascend_n(2), Err(TreeError::NoFather));
+        assert_eq!(tree.peek(), &3);
+        tree.navigate_path(&[1, 0]);
+        assert_eq!(tree.peek(), &6);
+        tree.ascend_n(3);
+        assert!(tree.path().is_empty());
+
+        let mut cursor = tree.cursor_root();
+        cursor.navigate_path(&[2, 0]);
+        assert_eq!(cursor.peek(), &4);
+        assert_eq!(cursor.path(), vec![2, 0]);
+        cursor.ascend_n(1);
+        assert_eq!(cursor.child_index(), Some(2));
+    }
+
+    #[test]
+    fn comparison() {
+        let mut a = Tree::from_element(0);
+        a.push_iter(vec![1, 2]);
+        a.navigate_to(0);
+        a.push(3);
+        let mut b = a.clone();
+        b.go_to_root();
+        assert_eq!(a, b);
+
+        let hash = |tree: &Tree<i32>| {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            tree.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&a), hash(&b));
+
+        // same pre-order elements, different shape
+        let mut c = Tree::from_element(0);
+        c.push_iter(vec![1, 3, 2]);
+        assert_ne!(a, c);
+        assert!(a < c);
+        assert_eq!(chain(100_000), chain(100_000));
+
+        let empty = Tree::default();
+        assert!(empty < a);
+        assert_eq!(empty, Tree::default());
+
+        let mut sorted = [c.clone(), empty, a.clone()];
+        sorted.sort();
+        assert_eq!(sorted, [Tree::default(), a, c]);
+    }
+
+    #[test]
+    fn debug() {
+        let mut tree = Tree::from_element(0);
+        tree.push_iter(vec![1, 2]);
+        tree.navigate_to(0);
+        tree.push(3);
+        assert_eq!(format!("{:?}", tree), "Tree(0 [1 [3], 2])");
+        assert_eq!(
+            format!("{:#?}", tree),
+            "Tree(\n    0 [\n        1 [\n            3,\n        ],\n        2,\n    ],\n)"
+        );
+        assert_eq!(format!("{:?}", Tree::from_element(1)), "Tree(1)");
+        assert_eq!(format!("{:?}", Tree::<i32>::default()), "Tree()");
+        let deep = format!("{:?}", chain(100_000));
+        assert!(deep.starts_with("Tree(0 [1 [2 ["));
+        assert!(deep.ends_with(&format!("99998 [99999{})", "]".repeat(99_999))));
+    }
+
+    #[test]
+    fn map() {
+        let mut tree = Tree::from_element(0);
+        tree.push_iter(vec![1, 2]);
+        tree.navigate_to(0);
+        tree.push_iter(vec![3, 4]);
+        tree.navigate_to(1);
+        let handle = tree.handle();
+
+        let strings = tree.map_ref(|elem| elem.to_string());
+        assert_eq!(strings.peek(), "4");
+        assert_eq!(strings.path(), vec![0, 1]);
+        assert_eq!(strings.to_sexpr(), "(0 (1 3 4) 2)");
+
+        let doubled = tree.map(|elem| elem * 2);
+        assert!(!handle.is_alive());
+        assert_eq!(doubled.peek(), &8);
+        assert_eq!(doubled.to_sexpr(), "(0 (2 6 8) 4)");
+
+        let mut count = 0;
+        let result = strings.clone().try_map(|elem| {
+            count += 1;
+            if elem == "3" {
+                Err(elem)
+            } else {
+                Ok(elem.len())
+            }
+        });
+        assert_eq!(result, Err(String::from("3")));
+        assert_eq!(count, 3);
+
+        assert!(Tree::<i32>::default().map(|elem| elem + 1).is_empty());
+        assert_eq!(chain(100_000).map(|elem| elem + 1).depth(), 99_999);
+
+        // every element is dropped, mapped or not, when f panics
+        let shared = Rc::new(());
+        let mut tree = Tree::from_element(Rc::clone(&shared));
+        tree.push_iter(vec![Rc::clone(&shared), Rc::clone(&shared)]);
+        tree.navigate_to(0);
+        tree.push_iter(vec![Rc::clone(&shared), Rc::clone(&shared)]);
+        let mut count = 0;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            tree.map(|elem| {
+                count += 1;
+                if count == 3 {
+                    panic!("map failed");
+                }
+                elem
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(Rc::strong_count(&shared), 1);
+    }
+
+    #[test]
+    fn fold() {
+        let mut tree: Tree<i32> = "(1 (2 3 4) (5 6))".parse().unwrap();
+        let heights = tree.fold_to_tree(|_, childs: Vec<&usize>| {
+            childs.into_iter().max().map_or(0, |height| height + 1)
+        });
+        assert_eq!(heights.to_sexpr(), "(2 (1 0 0) (1 0))");
+        assert_eq!(heights.peek(), &2);
+
+        tree.navigate_to(0);
+        let elems = tree.fold(|elem, childs: Vec<Vec<i32>>| {
+            let mut elems = vec![*elem];
+            elems.extend(childs.into_iter().flatten());
+            elems
+        });
+        assert_eq!(elems, vec![2, 3, 4]);
+        assert_eq!(tree.fold_to_tree(|elem, _| *elem).to_sexpr(), "(2 3 4)");
+
+        let deep = chain(1_000_000);
+        let cursor = deep.cursor_root();
+        assert_eq!(
+            cursor.fold(|_, childs: Vec<usize>| childs.len() + childs.iter().sum::<usize>()),
+            999_999
+        );
+        assert_eq!(cursor.fold_to_tree(|elem, _| *elem).height(), 999_999);
+
+        // every result already built is dropped when f panics
+        tree.go_to_root();
+        let shared = Rc::new(());
+        let mut count = 0;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            tree.fold_to_tree(|_, _: Vec<&Rc<()>>| {
+                count += 1;
+                if count == 4 {
+                    panic!("fold failed");
+                }
+                Rc::clone(&shared)
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(Rc::strong_count(&shared), 1);
+    }
+
     #[test]
     fn clone() {
         let mut tree = Tree::from_element(0);
@@ -1194,6 +3043,90 @@ mod test {
         tree.peek();
     }
 
+    /// Build a chain of n nodes, leaving 'current' on the deepest one.
+    fn chain(n: usize) -> Tree<usize> {
+        let mut tree = Tree::from_element(0);
+        for i in 1..n {
+            tree.push(i);
+            tree.navigate_to(0);
+        }
+        tree
+    }
+
+    #[test]
+    fn reroot() {
+        let mut tree = Tree::from_element(0);
+        tree.push_iter(vec![1, 2, 3]);
+        tree.navigate_to(2);
+        tree.push_iter(vec![4, 5]);
+        tree.navigate_to(1);
+        let kept = tree.handle();
+        tree.go_to_root();
+        tree.navigate_to(0);
+        let freed = tree.handle();
+        tree.ascend();
+
+        tree.promote_child(2);
+        assert_eq!(tree.peek(), &3);
+        assert!(!tree.has_father());
+        assert!(!freed.is_alive());
+        tree.navigate_to_handle(&kept).unwrap();
+        assert_eq!(tree.path(), vec![1]);
+
+        // already at 'root'
+        tree.go_to_root();
+        tree.reroot_at_current();
+        assert_eq!(tree.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5]);
+        tree.navigate_to(0);
+        tree.reroot_at_current();
+        assert_eq!(tree.into_vec(), vec![4]);
+
+        let mut tree = chain(1_000_000);
+        tree.reroot_at_current();
+        assert_eq!(tree.peek(), &999_999);
+        assert_eq!(tree.subtree_len(), 1);
+        tree.go_to_root();
+        assert_eq!(tree.peek(), &999_999);
+    }
+
+    #[test]
+    fn deep_tree() {
+        const DEPTH: usize = 1_000_000;
+        let mut tree = chain(DEPTH);
+        assert_eq!(tree.peek(), &(DEPTH - 1));
+        tree.go_to_root();
+
+        assert_eq!(tree.iter().count(), DEPTH);
+        assert_eq!(tree.iter_mut().count(), DEPTH);
+        assert_eq!(tree.lazyiter().count(), DEPTH);
+        assert_eq!(tree.lazyiter_mut().count(), DEPTH);
+        assert_eq!(tree.iter_postorder().next(), Some(&(DEPTH - 1)));
+        assert_eq!(tree.iter_bfs().count(), DEPTH);
+
+        let clone = tree.clone();
+        assert_eq!(clone.iter().last(), Some(&(DEPTH - 1)));
+        std::mem::drop(clone);
+
+        let mut clone = tree.clone();
+        clone.navigate_to(0);
+        assert_eq!(clone.into_vec().len(), DEPTH - 1);
+        assert_eq!(Vec::from(tree.clone()).len(), DEPTH);
+        assert_eq!(tree.clone().into_iter_postorder().next(), Some(DEPTH - 1));
+
+        let split_tree = tree.split(0);
+        std::mem::drop(split_tree);
+        std::mem::drop(tree);
+
+        // dropped without ever navigating back to root
+        std::mem::drop(chain(DEPTH));
+    }
+
+    #[test]
+    fn lazyiter_single_node() {
+        let tree = Tree::from_element(1);
+        assert_eq!(tree.lazyiter().collect::<Vec<&i32>>(), vec![&1]);
+    }
+
     #[test]
     fn lazyiter() {
         let mut tree = Tree::from_element(0);